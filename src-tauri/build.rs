@@ -1,3 +1,15 @@
+use std::process::Command;
+
 fn main() {
+    // Build info for get_app_info; the commit is optional since source tarballs have no .git
+    if let Ok(output) = Command::new("git").args(["rev-parse", "--short", "HEAD"]).output() {
+        if output.status.success() {
+            let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            println!("cargo:rustc-env=VRCPARAM_GIT_COMMIT={}", commit);
+        }
+    }
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+    println!("cargo:rustc-env=VRCPARAM_TARGET={}", std::env::var("TARGET").unwrap_or_default());
+    
     tauri_build::build()
 }
@@ -0,0 +1,231 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use tauri::State;
+
+use crate::osc::{parameter_address, parameter_name_from_address, parse_parameter_type, OscState, ParameterType};
+
+// VRChat's generated avatar OSC config (OSC/usr_*/Avatars/avtr_*.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvatarConfig {
+    pub id: String,
+    pub name: String,
+    pub parameters: Vec<AvatarConfigParameter>,
+    // Expression menu hierarchy, when the file was exported with one. VRChat's own generated
+    // configs don't include it
+    #[serde(default)]
+    pub menu: Option<AvatarMenu>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvatarMenu {
+    pub name: String,
+    #[serde(default)]
+    pub controls: Vec<AvatarMenuControl>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvatarMenuControl {
+    pub name: String,
+    #[serde(default)]
+    pub parameter: Option<String>,
+    #[serde(default)]
+    pub sub_menu: Option<AvatarMenu>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvatarConfigParameter {
+    pub name: String,
+    // Present when the parameter can be driven over OSC
+    #[serde(default)]
+    pub input: Option<AvatarConfigEndpoint>,
+    #[serde(default)]
+    pub output: Option<AvatarConfigEndpoint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvatarConfigEndpoint {
+    pub address: String,
+    #[serde(rename = "type")]
+    pub parameter_type: String,
+}
+
+impl AvatarConfig {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let json = fs::read_to_string(path).map_err(|e| format!("Failed to read avatar config: {}", e))?;
+        // VRChat writes these files with a UTF-8 BOM
+        serde_json::from_str(json.trim_start_matches('\u{feff}'))
+            .map_err(|e| format!("Failed to parse avatar config: {}", e))
+    }
+
+    // Declared type of each parameter, keyed by the name used in OSC addresses
+    pub fn parameter_types(&self) -> HashMap<String, ParameterType> {
+        self.parameters
+            .iter()
+            .filter_map(|param| {
+                let endpoint = param.input.as_ref().or(param.output.as_ref())?;
+                let name = parameter_name_from_address(&endpoint.address).unwrap_or(&param.name);
+                let parameter_type = parse_parameter_type(&endpoint.parameter_type).ok()?;
+                Some((name.to_string(), parameter_type))
+            })
+            .collect()
+    }
+
+    // Parameters VRChat only reports and won't accept (no input endpoint, e.g. VelocityX), by the
+    // name used in OSC addresses
+    pub fn read_only_parameters(&self) -> HashSet<String> {
+        self.parameters
+            .iter()
+            .filter(|param| param.input.is_none())
+            .map(|param| {
+                let address = param.output.as_ref().map(|endpoint| endpoint.address.as_str());
+                address.and_then(parameter_name_from_address).unwrap_or(&param.name).to_string()
+            })
+            .collect()
+    }
+
+    // "Submenu/Nested" path of the menu each parameter is controlled from. The root menu is left
+    // out, and a parameter used in several menus keeps the first one found
+    pub fn menu_paths(&self) -> HashMap<String, String> {
+        let mut paths = HashMap::new();
+        if let Some(menu) = &self.menu {
+            collect_menu_paths(menu, "", &mut paths);
+        }
+        paths
+    }
+
+    // Input addresses that aren't the usual /avatar/parameters/<name>, keyed by parameter name
+    pub fn custom_addresses(&self) -> HashMap<String, String> {
+        self.parameters
+            .iter()
+            .filter_map(|param| {
+                let address = &param.input.as_ref()?.address;
+                (*address != parameter_address(&param.name)).then(|| (param.name.clone(), address.clone()))
+            })
+            .collect()
+    }
+}
+
+fn collect_menu_paths(menu: &AvatarMenu, path: &str, paths: &mut HashMap<String, String>) {
+    for control in &menu.controls {
+        if let Some(parameter) = &control.parameter {
+            if !path.is_empty() {
+                paths.entry(parameter.clone()).or_insert_with(|| path.to_string());
+            }
+        }
+        if let Some(sub_menu) = &control.sub_menu {
+            let sub_path = if path.is_empty() {
+                control.name.clone()
+            } else {
+                format!("{}/{}", path, control.name)
+            };
+            collect_menu_paths(sub_menu, &sub_path, paths);
+        }
+    }
+}
+
+// Tauri commands
+
+#[tauri::command]
+pub fn import_avatar_config(path: &str, osc_state: State<OscState>) -> Result<usize, String> {
+    let config = AvatarConfig::load(path)?;
+    let count = config.parameters.len();
+    osc_state.set_avatar_config(Some(config))?;
+    
+    Ok(count)
+}
+
+#[tauri::command]
+pub fn apply_types_from_config(avatar_id: &str, osc_state: State<OscState>) -> Result<usize, String> {
+    osc_state.apply_types_from_config(avatar_id)
+}
+
+#[tauri::command]
+pub fn clear_avatar_config(osc_state: State<OscState>) -> Result<(), String> {
+    osc_state.set_avatar_config(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_CONFIG: &str = r#"{
+        "id": "avtr_test",
+        "name": "Test",
+        "parameters": [
+            {
+                "name": "Hat",
+                "input": { "address": "/avatar/parameters/Hat", "type": "Bool" },
+                "output": { "address": "/avatar/parameters/Hat", "type": "Bool" }
+            },
+            {
+                "name": "VelocityX",
+                "output": { "address": "/avatar/parameters/VelocityX", "type": "Float" }
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn parameters_without_input_are_read_only() {
+        let config: AvatarConfig = serde_json::from_str(SAMPLE_CONFIG).unwrap();
+        let read_only = config.read_only_parameters();
+        
+        assert!(read_only.contains("VelocityX"));
+        assert!(!read_only.contains("Hat"));
+    }
+
+    #[test]
+    fn types_come_from_either_endpoint() {
+        let config: AvatarConfig = serde_json::from_str(SAMPLE_CONFIG).unwrap();
+        let types = config.parameter_types();
+        
+        assert_eq!(types.get("Hat"), Some(&ParameterType::Bool));
+        assert_eq!(types.get("VelocityX"), Some(&ParameterType::Float));
+    }
+
+    #[test]
+    fn menu_paths_follow_nested_submenus() {
+        let config: AvatarConfig = serde_json::from_str(r#"{
+            "id": "avtr_test",
+            "name": "Test",
+            "parameters": [],
+            "menu": {
+                "name": "Root",
+                "controls": [
+                    { "name": "Sparkles", "parameter": "Sparkles" },
+                    {
+                        "name": "Outfits",
+                        "subMenu": {
+                            "name": "Outfits",
+                            "controls": [
+                                { "name": "Cap", "parameter": "Hat/Cap" },
+                                {
+                                    "name": "Hats",
+                                    "subMenu": {
+                                        "name": "Hats",
+                                        "controls": [
+                                            { "name": "Beanie", "parameter": "Hat/Beanie" },
+                                            { "name": "Cap again", "parameter": "Hat/Cap" }
+                                        ]
+                                    }
+                                }
+                            ]
+                        }
+                    }
+                ]
+            }
+        }"#).unwrap();
+        let paths = config.menu_paths();
+        
+        assert_eq!(paths.get("Hat/Beanie").map(String::as_str), Some("Outfits/Hats"));
+        assert_eq!(paths.get("Hat/Cap").map(String::as_str), Some("Outfits"));
+        assert!(!paths.contains_key("Sparkles"));
+    }
+
+    #[test]
+    fn config_without_menu_has_no_paths() {
+        let config: AvatarConfig = serde_json::from_str(SAMPLE_CONFIG).unwrap();
+        assert!(config.menu_paths().is_empty());
+    }
+}
@@ -1,11 +1,24 @@
-use std::sync::Arc;
+use tauri::{Manager, RunEvent};
 
+mod avatar_config;
+mod listener_lock;
+mod logging;
 mod osc;
+#[cfg(feature = "oscquery")]
+mod oscquery;
+mod persist;
+mod preset;
+mod session;
 mod speech;
 
 use osc::OscState;
+use preset::PresetState;
 use speech::SpeechState;
 
+fn no_autostart() -> bool {
+    std::env::var("VRCPARAM_NO_AUTOSTART").map_or(false, |v| !v.is_empty() && v != "0")
+}
+
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -14,31 +27,43 @@ fn greet(name: &str) -> String {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let osc_state = Arc::new(OscState::new());
+    let osc_state = OscState::new();
     let speech_state = SpeechState::new();
+    let preset_state = PresetState::new();
     
     tauri::Builder::default()
+        .plugin(logging::plugin())
         .plugin(tauri_plugin_opener::init())
-        .manage(osc_state.clone())
+        .plugin(tauri_plugin_notification::init())
+        .manage(osc_state)
         .manage(speech_state)
+        .manage(preset_state)
         .setup(|app| {
             let app_handle = app.handle();
             
             // Get managed states and initialize them with the app handle
             let speech_state = app.state::<SpeechState>();
-            let mut speech_state_mut = speech_state.inner().clone();
             
-            if let Err(e) = speech_state_mut.initialize(app_handle.clone()) {
+            if let Err(e) = speech_state.initialize(app_handle.clone()) {
                 log::error!("Failed to initialize speech state: {}", e);
             }
             
+            let preset_state = app.state::<PresetState>();
+            if let Err(e) = preset_state.initialize(app_handle.clone()) {
+                log::error!("Failed to initialize preset state: {}", e);
+            }
+            
             // Initialize OSC state
             let osc_state = app.state::<OscState>();
             osc_state.initialize(app_handle.clone());
             
-            // Start the OSC listener in a separate thread
-            if let Err(e) = osc_state.start_listener() {
+            // Start the OSC listener in a separate thread, unless the user wants to start it
+            // from the UI (e.g. when another instance already holds the port)
+            if no_autostart() {
+                log::info!("VRCPARAM_NO_AUTOSTART is set, not starting the OSC listener");
+            } else if let Err(e) = osc_state.start_listener() {
                 log::error!("Failed to start OSC listener: {}", e);
+                osc_state.emit_event("osc-error", format!("Failed to start OSC listener: {}", e));
             }
             
             Ok(())
@@ -46,15 +71,118 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             greet,
             osc::get_all_parameters,
+            osc::refresh_parameters,
+            osc::request_full_state,
+            osc::rename_parameter,
+            osc::remove_parameters_matching,
+            osc::export_state,
+            osc::import_state,
+            osc::export_config,
+            osc::import_config,
+            osc::get_meta_parameters,
+            osc::get_parameters_grouped,
+            osc::suggest_parameters,
+            osc::is_local,
+            osc::set_update_subscription,
+            osc::set_send_debug_watch,
+            osc::get_parameter_schema,
+            osc::is_valid_parameter_type,
+            osc::list_parameter_types,
             osc::set_parameter_value,
+            osc::set_parameter_by_index,
+            osc::set_parameter_values,
+            osc::set_parameters,
+            osc::send_parameter_to,
+            osc::send_bool,
+            osc::set_bool_threshold,
+            osc::set_float_deadband,
+            osc::set_max_packet_size,
+            osc::set_queue_mode,
+            osc::inject_parameter,
+            osc::set_display_label,
+            osc::set_parameter_curve,
+            osc::set_parameter_send_curve,
+            osc::set_parameter_writable,
+            osc::resolve_send_type,
+            osc::add_parameter_watch,
+            osc::remove_parameter_watch,
+            osc::get_parameter_watches,
             osc::update_osc_config,
             osc::get_osc_config,
+            osc::reset_osc_config,
+            osc::set_allow_loopback,
+            osc::set_parameter_filters,
+            osc::seconds_since_last_packet,
+            osc::get_filtered_message_count,
+            osc::get_unsupported_message_count,
+            osc::set_send_bind_address,
+            osc::set_decode_error_threshold,
+            osc::get_decode_error_count,
+            osc::get_emit_failure_count,
+            osc::get_send_bandwidth,
+            osc::get_parameter_rates,
+            osc::get_observed_addresses,
+            osc::set_debug_network,
+            osc::set_send_rate_limit,
+            osc::start_send_burst,
+            osc::end_send_burst,
+            osc::set_event_debounce_ms,
             osc::restart_osc_listener,
+            osc::get_listen_address,
+            osc::pause_listener,
+            osc::resume_listener,
+            osc::is_listener_paused,
+            osc::test_osc_send,
+            osc::await_parameter_change,
+            osc::query_parameter,
+            osc::set_and_confirm,
+            #[cfg(feature = "oscquery")]
+            oscquery::fetch_vrchat_parameters,
+            preset::get_presets,
+            preset::apply_preset,
+            preset::preview_preset_apply,
+            preset::cancel_preset_apply,
+            preset::diff_presets,
+            preset::remove_preset,
+            preset::snapshot_to_preset,
+            avatar_config::import_avatar_config,
+            avatar_config::clear_avatar_config,
+            avatar_config::apply_types_from_config,
+            session::export_session,
+            session::reload_all_state,
+            session::get_app_info,
+            logging::get_log_file_path,
             speech::add_command,
+            speech::add_commands,
             speech::remove_command,
+            speech::copy_command,
             speech::get_command_mappings,
+            speech::get_all_command_mappings,
+            speech::count_commands,
+            speech::merge_languages,
+            speech::set_default_parameter_type,
+            speech::get_default_parameter_type,
+            speech::set_speech_enabled,
+            speech::is_speech_enabled,
+            speech::set_spoken_numbers,
+            speech::set_normalize_parameter_names,
+            speech::set_case_insensitive_matching,
+            speech::validate_commands,
+            speech::set_commands_watch,
             speech::process_speech,
+            speech::preview_speech_matches,
+            speech::test_speech_regex,
+            speech::get_speech_history,
+            speech::clear_speech_history,
+            speech::get_speech_stats,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            // Stop the listener and flush pending sends so a quick restart can rebind the port
+            if let RunEvent::ExitRequested { .. } | RunEvent::Exit = event {
+                app_handle.state::<OscState>().shutdown();
+                app_handle.state::<SpeechState>().shutdown();
+            }
+        });
 }
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+use crate::persist::instance_id;
+
+// Record of which process is listening on a port. Lock files live in the shared app data
+// directory rather than the per-instance one, so every instance sees every other's locks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListenerLock {
+    pub port: u16,
+    pub pid: u32,
+    pub instance: Option<String>,
+}
+
+fn lock_path(app_data: &Path, port: u16) -> PathBuf {
+    app_data.join("locks").join(format!("listener-{}.lock", port))
+}
+
+// Another live process holding the lock for `port`, if any. Locks left behind by processes
+// that have exited are removed
+pub fn find_conflict(app_data: &Path, port: u16) -> Option<ListenerLock> {
+    let path = lock_path(app_data, port);
+    let lock: ListenerLock = serde_json::from_str(&fs::read_to_string(&path).ok()?).ok()?;
+
+    if lock.pid == process::id() {
+        return None;
+    }
+    if !process_alive(lock.pid) {
+        log::info!("Removing stale listener lock for port {} (PID {})", port, lock.pid);
+        let _ = fs::remove_file(&path);
+        return None;
+    }
+    Some(lock)
+}
+
+pub fn acquire(app_data: &Path, port: u16) -> Result<(), String> {
+    let path = lock_path(app_data, port);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create locks directory: {}", e))?;
+    }
+
+    let lock = ListenerLock {
+        port,
+        pid: process::id(),
+        instance: instance_id().map(str::to_string),
+    };
+    let json = serde_json::to_string(&lock).map_err(|e| format!("Failed to serialize listener lock: {}", e))?;
+    fs::write(path, json).map_err(|e| format!("Failed to write listener lock: {}", e))
+}
+
+// Remove our lock for `port`; a lock taken over by another process is left alone
+pub fn release(app_data: &Path, port: u16) {
+    let path = lock_path(app_data, port);
+    let ours = fs::read_to_string(&path)
+        .ok()
+        .and_then(|json| serde_json::from_str::<ListenerLock>(&json).ok())
+        .map_or(false, |lock| lock.pid == process::id());
+
+    if ours {
+        let _ = fs::remove_file(path);
+    }
+}
+
+#[cfg(windows)]
+fn process_alive(pid: u32) -> bool {
+    // tasklist prints an info line instead of a row when nothing matches the filter
+    Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/NH", "/FO", "CSV"])
+        .output()
+        .map_or(true, |output| String::from_utf8_lossy(&output.stdout).contains(&format!("\"{}\"", pid)))
+}
+
+#[cfg(not(windows))]
+fn process_alive(pid: u32) -> bool {
+    // Signal 0 only checks that the process exists
+    Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .output()
+        .map_or(true, |output| output.status.success())
+}
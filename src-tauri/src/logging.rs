@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_log::{RotationStrategy, Target, TargetKind};
+
+const LOG_FILE_NAME: &str = "vrcparam";
+// Roll over at 5 MB and keep only the previous file, so logs use at most ~10 MB of disk
+const MAX_LOG_FILE_BYTES: u128 = 5 * 1024 * 1024;
+
+// Log level from VRCPARAM_LOG_LEVEL (error, warn, info, debug, trace), info by default.
+// Read from the environment since logging starts before the config is loaded
+fn log_level() -> log::LevelFilter {
+    std::env::var("VRCPARAM_LOG_LEVEL")
+        .ok()
+        .and_then(|level| level.parse().ok())
+        .unwrap_or(log::LevelFilter::Info)
+}
+
+// Logs go to stdout and a rotating file in the app log directory
+pub fn plugin<R: Runtime>() -> tauri::plugin::TauriPlugin<R> {
+    tauri_plugin_log::Builder::new()
+        .targets([
+            Target::new(TargetKind::Stdout),
+            Target::new(TargetKind::LogDir {
+                file_name: Some(LOG_FILE_NAME.to_string()),
+            }),
+        ])
+        .level(log_level())
+        .max_file_size(MAX_LOG_FILE_BYTES)
+        .rotation_strategy(RotationStrategy::KeepOne)
+        .build()
+}
+
+fn log_file_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    let log_dir = app_handle
+        .path()
+        .app_log_dir()
+        .map_err(|e| format!("Failed to get log directory: {}", e))?;
+    Ok(log_dir.join(format!("{}.log", LOG_FILE_NAME)))
+}
+
+// Tauri commands
+
+#[tauri::command]
+pub fn get_log_file_path(app_handle: AppHandle) -> Result<String, String> {
+    log_file_path(&app_handle).map(|path| path.to_string_lossy().to_string())
+}
@@ -1,26 +1,180 @@
 use anyhow::{anyhow, Result};
-use rosc::{OscMessage, OscPacket, OscType};
-use serde::{Deserialize, Serialize};
+use rosc::{OscBundle, OscColor, OscMessage, OscPacket, OscTime, OscType};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    fs,
     net::{SocketAddr, UdpSocket},
+    path::PathBuf,
     str::FromStr,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc, Arc, Condvar, Mutex, OnceLock,
+    },
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
-use tauri::{AppHandle, Manager, State};
+use tauri::{AppHandle, Emitter, Manager, State, Window};
+use tauri_plugin_notification::NotificationExt;
+use tokio::sync::oneshot;
+
+use crate::avatar_config::AvatarConfig;
+use crate::listener_lock;
+use crate::persist::{from_versioned_json, instance_data_dir, instance_id, no_migrations, set_compact_json, to_versioned_json};
 
 // Default OSC addresses for VRChat
 const DEFAULT_VRC_ADDRESS: &str = "127.0.0.1:9000"; // Send to VRChat
 const DEFAULT_LISTEN_ADDRESS: &str = "127.0.0.1:9001"; // Listen from VRChat
+const DEFAULT_SEND_BIND_ADDRESS: &str = "0.0.0.0:0"; // Let the OS pick the outbound interface
+
+// Built-in parameters VRChat reports under the avatar prefix for every avatar
+const DEFAULT_META_PARAMETERS: [&str; 12] = [
+    "IsLocal",
+    "Viseme",
+    "Voice",
+    "GestureLeft",
+    "GestureRight",
+    "TrackingType",
+    "VRMode",
+    "AFK",
+    "MuteSelf",
+    "InStation",
+    "Seated",
+    "Grounded",
+];
+
+// Avatar parameter names may themselves contain slashes (e.g. "group/sub" for contacts and physbones)
+pub const AVATAR_PARAMETER_PREFIX: &str = "/avatar/parameters/";
+const INPUT_PREFIX: &str = "/input/";
+const CHATBOX_INPUT_ADDRESS: &str = "/chatbox/input";
+
+// VRChat inputs that take a float in [-1, 1]; every other input is a 0/1 button
+const INPUT_AXES: [&str; 9] = [
+    "Vertical",
+    "Horizontal",
+    "LookHorizontal",
+    "UseAxisRight",
+    "GrabAxisRight",
+    "MoveHoldFB",
+    "SpinHoldCwCcw",
+    "SpinHoldUD",
+    "SpinHoldLR",
+];
+// Buttons only register on a 0 -> 1 transition, so presses are released after this long
+const INPUT_BUTTON_RELEASE: Duration = Duration::from_millis(100);
+
+// Float values strictly above this are sent as `true` for Bool parameters
+const DEFAULT_BOOL_THRESHOLD: f32 = 0.5;
+
+// Decode failures are logged individually up to this count, then only reported in aggregate
+const DECODE_ERROR_LOG_LIMIT: u64 = 5;
+const DEFAULT_DECODE_ERROR_THRESHOLD: u64 = 50;
+
+// Listener error handling: back off between retries, rebind after repeated failures
+const LISTENER_BACKOFF_MIN_MS: u64 = 100;
+const LISTENER_BACKOFF_MAX_MS: u64 = 5000;
+const LISTENER_MAX_CONSECUTIVE_ERRORS: u32 = 5;
+
+// Watchdog for a listener thread that exits while it should be running (e.g. a panic)
+const WATCHDOG_POLL: Duration = Duration::from_secs(1);
+// A listener that stays up this long resets the restart backoff
+const WATCHDOG_STABLE_AFTER: Duration = Duration::from_secs(60);
+
+const DEFAULT_STOP_TIMEOUT_MS: u64 = 1000;
+
+// Connectivity test: VRChat ignores unknown addresses, so this send has no side effects
+const DEFAULT_PING_ADDRESS: &str = "/vrcparam/ping";
+const DEFAULT_PING_TIMEOUT_MS: u64 = 1000;
+
+// Minimum gap between desktop notifications for the same watch, so flapping values don't spam
+const WATCH_NOTIFY_INTERVAL: Duration = Duration::from_secs(30);
+
+// Outbound sends from the UI are paced at this rate by default
+const DEFAULT_SEND_RATE_HZ: u32 = 30;
+const MIN_SEND_RATE_HZ: u32 = 1;
+const MAX_SEND_RATE_HZ: u32 = 200;
+const MAX_EVENT_DEBOUNCE_MS: u64 = 5000;
+
+const DEFAULT_SUGGESTION_LIMIT: usize = 10;
+
+// VRChat quantizes synced floats, so an echo within this distance counts as confirmation
+const DEFAULT_CONFIRM_TOLERANCE: f32 = 0.01;
+
+// How sharply exponential/logarithmic send curves bend
+const SEND_CURVE_STEEPNESS: f32 = 4.0;
+
+// Bundles are split to stay under this many bytes, a safe UDP payload for a typical 1500 MTU
+const DEFAULT_MAX_PACKET_SIZE: usize = 1400;
+const MIN_MAX_PACKET_SIZE: usize = 256;
+const MAX_MAX_PACKET_SIZE: usize = 65_000;
+// "#bundle\0" plus the timetag, and the length prefix in front of each bundle element
+const BUNDLE_HEADER_SIZE: usize = 16;
+const BUNDLE_ELEMENT_PREFIX_SIZE: usize = 4;
+
+// Distinct inbound addresses remembered for get_observed_addresses; the least recently seen is
+// dropped beyond this
+const MAX_OBSERVED_ADDRESSES: usize = 1024;
+
+// Retry queue for sends that failed at the socket level
+const RETRY_MAX_ATTEMPTS: u32 = 3;
+const RETRY_BACKOFF_MS: u64 = 200;
+const RETRY_QUEUE_LIMIT: usize = 64;
+const RETRY_POLL_MS: u64 = 50;
 
 // Parameter types supported by VRChat
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ParameterType {
     Float,
     Int,
     Bool,
+    // RGBA in a single OSC color argument; channels are kept as 0-1 floats in `values`
+    Color,
+}
+
+impl ParameterType {
+    pub const ALL: [ParameterType; 4] = [
+        ParameterType::Float,
+        ParameterType::Int,
+        ParameterType::Bool,
+        ParameterType::Color,
+    ];
+
+    // Reject values that make no sense for this type: bools must be 0 or 1, ints whole numbers,
+    // and everything within range()
+    pub fn validate_value(&self, value: f32) -> Result<(), String> {
+        let (min, max) = self.range();
+        if !value.is_finite() || value < min || value > max {
+            return Err(format!("{} value must be between {} and {}, got {}", self.as_str(), min, max, value));
+        }
+        match self {
+            ParameterType::Bool if value != 0.0 && value != 1.0 => {
+                Err(format!("Bool value must be 0 or 1, got {}", value))
+            }
+            ParameterType::Int if value.fract() != 0.0 => {
+                Err(format!("Int value must be a whole number, got {}", value))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Value range VRChat accepts for this type
+    pub fn range(&self) -> (f32, f32) {
+        match self {
+            ParameterType::Float => (-1.0, 1.0),
+            ParameterType::Int => (0.0, 255.0),
+            ParameterType::Bool => (0.0, 1.0),
+            ParameterType::Color => (0.0, 1.0),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParameterType::Float => "Float",
+            ParameterType::Int => "Int",
+            ParameterType::Bool => "Bool",
+            ParameterType::Color => "Color",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,196 +182,548 @@ pub struct Parameter {
     pub name: String,
     pub parameter_type: ParameterType,
     pub value: f32,
+    // Friendly name shown in the UI only; OSC always uses `name`
+    #[serde(default)]
+    pub display_name: Option<String>,
+    // `value` shaped by the parameter's response curve, if one is attached
+    #[serde(default)]
+    pub shaped_value: Option<f32>,
+    // All arguments of a multi-value message; `value` holds the first one
+    #[serde(default)]
+    pub values: Option<Vec<f32>>,
+    // False for output-only parameters (e.g. VelocityX) that must never be sent back
+    #[serde(default = "default_writable")]
+    pub writable: bool,
+    // VRChat built-in state (IsLocal, Viseme, ...) rather than an avatar-defined parameter
+    #[serde(default)]
+    pub is_meta: bool,
+    // Sent to verbatim instead of /avatar/parameters/<name>, for prefabs with their own address
+    #[serde(default)]
+    pub osc_address: Option<String>,
+    // Position in the name-sorted parameter list, for controllers that address by index. Only
+    // set on parameter lists, and shifts whenever a parameter is discovered or removed
+    #[serde(default)]
+    pub index: Option<usize>,
+    // Value last set from the UI before its send curve; `value` holds what was actually sent
+    #[serde(default)]
+    pub ui_value: Option<f32>,
+    // Expression submenu the parameter lives in, from the imported avatar config
+    #[serde(default)]
+    pub menu_path: Option<String>,
+}
+
+fn default_writable() -> bool {
+    true
 }
 
+// Condition a parameter watch checks against each new value
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct OscConfig {
-    pub target_address: String,
-    pub target_port: u16,
-    pub listen_address: String,
-    pub listen_port: u16,
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WatchCondition {
+    GreaterThan { threshold: f32 },
+    LessThan { threshold: f32 },
+    Equals { threshold: f32 },
+    InRange { min: f32, max: f32 },
 }
 
-impl Default for OscConfig {
-    fn default() -> Self {
-        Self {
-            target_address: "127.0.0.1".to_string(),
-            target_port: 9000,
-            listen_address: "127.0.0.1".to_string(),
-            listen_port: 9001,
+impl WatchCondition {
+    pub fn parse(comparison: &str, threshold: f32, max: Option<f32>) -> Result<Self, String> {
+        match comparison {
+            ">" => Ok(WatchCondition::GreaterThan { threshold }),
+            "<" => Ok(WatchCondition::LessThan { threshold }),
+            "==" => Ok(WatchCondition::Equals { threshold }),
+            "range" => match max {
+                Some(max) if max >= threshold => Ok(WatchCondition::InRange { min: threshold, max }),
+                Some(_) => Err("Range maximum must not be below the minimum".into()),
+                None => Err("Range comparison requires a maximum".into()),
+            },
+            _ => Err(format!("Invalid comparison: {}", comparison)),
+        }
+    }
+
+    pub fn matches(&self, value: f32) -> bool {
+        match self {
+            WatchCondition::GreaterThan { threshold } => value > *threshold,
+            WatchCondition::LessThan { threshold } => value < *threshold,
+            WatchCondition::Equals { threshold } => (value - threshold).abs() < f32::EPSILON * 16.0,
+            WatchCondition::InRange { min, max } => value >= *min && value <= *max,
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterWatch {
+    pub id: u64,
+    pub parameter_name: String,
+    pub condition: WatchCondition,
+    // Also show a native desktop notification when the alert fires
+    #[serde(default)]
+    pub notify_os: bool,
+    // Whether the condition held on the last update; alerts fire only on the false -> true edge
+    #[serde(skip)]
+    pub triggered: bool,
+    #[serde(skip)]
+    last_notified: Option<Instant>,
+}
+
+// How queued sends for a parameter behave under the send rate limit
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum QueueMode {
+    // Only the latest pending value is sent
+    #[default]
+    Coalesce,
+    // Every queued value is sent, in order
+    Ordered,
+}
+
+#[derive(Debug, Clone)]
+struct QueuedSend {
+    name: String,
+    value: f32,
+    param_type: ParameterType,
+}
+
+// Parameters a window wants updates for, and whether one of them changed since its last emit
 #[derive(Debug, Default)]
-pub struct OscState {
-    parameters: Arc<Mutex<HashMap<String, Parameter>>>,
-    config: Arc<Mutex<OscConfig>>,
-    listener_thread: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
-    running: Arc<Mutex<bool>>,
-    app_handle: Arc<Mutex<Option<AppHandle>>>,
+struct UpdateSubscription {
+    names: HashSet<String>,
+    dirty: bool,
 }
 
-impl OscState {
-    pub fn new() -> Self {
-        Self {
-            parameters: Arc::new(Mutex::new(HashMap::new())),
-            config: Arc::new(Mutex::new(OscConfig::default())),
-            listener_thread: Arc::new(Mutex::new(None)),
-            running: Arc::new(Mutex::new(false)),
-            app_handle: Arc::new(Mutex::new(None)),
-        }
-    }
+// Temporary raise of the send rate, e.g. while a bulk update drains
+#[derive(Debug, Clone)]
+struct SendBurst {
+    id: u64,
+    multiplier: f32,
+    until: Instant,
+}
 
-    pub fn initialize(&self, app_handle: AppHandle) {
-        let mut app_handle_ref = self.app_handle.lock().unwrap();
-        *app_handle_ref = Some(app_handle);
+// Registration for the next reported value of a parameter. Dropping it removes the
+// registration, so an early return or timeout can't leave it behind
+struct ChangeWaiter<'a> {
+    state: &'a OscState,
+    rx: oneshot::Receiver<Parameter>,
+}
+
+impl Drop for ChangeWaiter<'_> {
+    fn drop(&mut self) {
+        self.rx.close();
+        self.state.change_waiters.lock().unwrap().retain(|(_, tx)| !tx.is_closed());
     }
+}
 
-    // Get all parameters
-    pub fn get_parameters(&self) -> Vec<Parameter> {
-        let params = self.parameters.lock().unwrap();
-        params.values().cloned().collect()
+// Burst that ends when dropped, so an early return or error can't leave the rate raised
+pub struct SendBurstGuard<'a> {
+    state: &'a OscState,
+    id: u64,
+}
+
+impl Drop for SendBurstGuard<'_> {
+    fn drop(&mut self) {
+        self.state.end_burst(self.id);
     }
+}
 
-    // Set parameter value
-    pub fn set_parameter(&self, name: &str, value: f32) -> Result<()> {
-        let mut params = self.parameters.lock().unwrap();
+#[derive(Debug, Clone)]
+struct RetrySend {
+    send: QueuedSend,
+    attempts: u32,
+    due: Instant,
+}
+
+// Payload of `send-failed`, emitted when a send is dropped after exhausting its retries
+#[derive(Debug, Clone, Serialize)]
+pub struct SendFailure {
+    pub parameter_name: String,
+    pub value: f32,
+    pub attempts: u32,
+    pub error: String,
+}
+
+// Manual snapshot of the live state, for hot-reload during development or a manual backup.
+// Unlike the per-file persistence it is only written and read on request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    pub parameters: Vec<Parameter>,
+    pub config: OscConfig,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+}
+
+// Static description of the parameter shape, so frontends don't hardcode it
+#[derive(Debug, Clone, Serialize)]
+pub struct ParameterSchema {
+    pub fields: Vec<SchemaField>,
+    pub types: Vec<ParameterTypeInfo>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaField {
+    pub name: &'static str,
+    pub field_type: &'static str,
+    pub optional: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ParameterTypeInfo {
+    pub name: &'static str,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl ParameterSchema {
+    pub fn describe() -> Self {
+        let field = |name, field_type, optional| SchemaField {
+            name,
+            field_type,
+            optional,
+        };
         
-        if let Some(param) = params.get_mut(name) {
-            param.value = value;
-            Ok(())
-        } else {
-            Err(anyhow!("Parameter not found: {}", name))
+        Self {
+            fields: vec![
+                field("name", "string", false),
+                field("parameter_type", "ParameterType", false),
+                field("value", "number", false),
+                field("display_name", "string", true),
+                field("shaped_value", "number", true),
+                field("values", "number[]", true),
+                field("writable", "boolean", false),
+                field("is_meta", "boolean", false),
+                field("osc_address", "string", true),
+                field("index", "number", true),
+                field("ui_value", "number", true),
+                field("menu_path", "string", true),
+            ],
+            types: ParameterType::ALL
+                .iter()
+                .map(|t| {
+                    let (min, max) = t.range();
+                    ParameterTypeInfo {
+                        name: t.as_str(),
+                        min,
+                        max,
+                    }
+                })
+                .collect(),
         }
     }
+}
 
-    // Add or update parameter
-    pub fn update_parameter(&self, param: Parameter) {
-        let mut params = self.parameters.lock().unwrap();
-        params.insert(param.name.clone(), param);
-        
-        // Notify frontend of parameter updates if app handle is available
-        if let Some(app_handle) = self.app_handle.lock().unwrap().as_ref() {
-            // We don't want to block on this, so we just try to emit and ignore errors
-            let _ = app_handle.emit_all("parameter-updated", self.get_parameters());
+#[derive(Debug, Clone, Serialize)]
+pub struct OscTestResult {
+    pub send_ok: bool,
+    pub send_error: Option<String>,
+    pub listener_running: bool,
+    // Whether any inbound packet arrived within the timeout after sending
+    pub response_observed: bool,
+}
+
+// Per-parameter outcome of a multi-parameter set
+#[derive(Debug, Clone, Serialize)]
+pub struct SetParameterResult {
+    pub name: String,
+    pub sent: bool,
+    pub error: Option<String>,
+}
+
+// Result of query_parameter: VRChat's reported value when it arrived in time, otherwise our
+// cached copy (None if the parameter is unknown)
+#[derive(Debug, Clone, Serialize)]
+pub struct ParameterQueryResult {
+    pub parameter: Option<Parameter>,
+    pub from_vrchat: bool,
+}
+
+// Parameters sharing a menu path or, without menu info, a leading name segment ("group/...")
+#[derive(Debug, Clone, Serialize)]
+pub struct ParameterGroup {
+    // Empty for parameters that fit no group
+    pub name: String,
+    pub from_menu: bool,
+    pub parameters: Vec<Parameter>,
+}
+
+// An OSC address seen by the listener this session, of any kind (not just avatar parameters)
+#[derive(Debug, Clone, Serialize)]
+pub struct ObservedAddress {
+    pub address: String,
+    pub count: u64,
+    pub last_seen_ms: u64,
+}
+
+// Outcome of set_and_confirm; `reported` is the last value VRChat echoed, matching or not
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfirmResult {
+    pub confirmed: bool,
+    pub reported: Option<Parameter>,
+}
+
+// Autocomplete candidate; `matched` holds the char indices of the name that matched the query
+#[derive(Debug, Clone, Serialize)]
+pub struct ParameterSuggestion {
+    pub name: String,
+    pub score: u32,
+    pub matched: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ParameterAlert {
+    pub watch_id: u64,
+    pub parameter_name: String,
+    pub value: f32,
+    pub condition: WatchCondition,
+}
+
+impl Parameter {
+    pub fn new(name: &str, parameter_type: ParameterType, value: f32) -> Self {
+        Self {
+            name: name.to_string(),
+            parameter_type,
+            value,
+            display_name: None,
+            shaped_value: None,
+            values: None,
+            writable: true,
+            is_meta: false,
+            osc_address: None,
+            index: None,
+            ui_value: None,
+            menu_path: None,
         }
     }
-    
-    // Update OSC configuration
-    pub fn update_config(&self, new_config: OscConfig) -> Result<()> {
-        let mut config = self.config.lock().unwrap();
-        
-        // Check if the configuration has changed
-        let config_changed = config.target_address != new_config.target_address 
-            || config.target_port != new_config.target_port
-            || config.listen_address != new_config.listen_address
-            || config.listen_port != new_config.listen_port;
-            
-        // Update config
-        *config = new_config;
-        
-        // If the configuration has changed and we're running, restart the listener
-        if config_changed {
-            drop(config);  // Release lock before calling other methods
-            
-            // Stop and restart the listener
-            if *self.running.lock().unwrap() {
-                self.stop_listener()?;
-                self.start_listener()?;
+}
+
+// Response curves applied to inbound values for display/visualization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ResponseCurve {
+    Linear,
+    EaseIn,
+    EaseOut,
+    // (input, output) points, interpolated linearly and clamped at the ends
+    Piecewise { points: Vec<(f32, f32)> },
+}
+
+impl ResponseCurve {
+    pub fn validate(&self) -> Result<(), String> {
+        if let ResponseCurve::Piecewise { points } = self {
+            if points.len() < 2 {
+                return Err("Piecewise curve needs at least two points".to_string());
+            }
+            if points.windows(2).any(|w| w[1].0 <= w[0].0) {
+                return Err("Piecewise curve points must have strictly increasing inputs".to_string());
             }
         }
-        
         Ok(())
     }
-    
-    // Get current OSC configuration
-    pub fn get_config(&self) -> OscConfig {
-        self.config.lock().unwrap().clone()
+
+    pub fn apply(&self, value: f32) -> f32 {
+        // Ease curves work on the magnitude so bipolar (-1..1) floats stay symmetric
+        let magnitude = value.abs().min(1.0);
+        match self {
+            ResponseCurve::Linear => value,
+            ResponseCurve::EaseIn => value.signum() * magnitude * magnitude,
+            ResponseCurve::EaseOut => value.signum() * (1.0 - (1.0 - magnitude) * (1.0 - magnitude)),
+            ResponseCurve::Piecewise { points } => {
+                let (first, last) = match (points.first(), points.last()) {
+                    (Some(first), Some(last)) => (first, last),
+                    _ => return value,
+                };
+                if value <= first.0 {
+                    return first.1;
+                }
+                if value >= last.0 {
+                    return last.1;
+                }
+                
+                for w in points.windows(2) {
+                    let ((x0, y0), (x1, y1)) = (w[0], w[1]);
+                    if value <= x1 {
+                        return y0 + (value - x0) / (x1 - x0) * (y1 - y0);
+                    }
+                }
+                last.1
+            }
+        }
     }
-    
-    // Start OSC listener with current configuration
-    pub fn start_listener(&self) -> Result<()> {
-        let mut running = self.running.lock().unwrap();
-        if *running {
-            return Ok(());  // Already running
+}
+
+// Curves applied to outgoing Float values, so a linear UI slider produces perceptually even
+// changes on parameters like light or audio intensity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SendCurve {
+    Linear,
+    // Fine control at the low end, e.g. brightness
+    Exponential,
+    // Fine control at the high end
+    Logarithmic,
+}
+
+impl SendCurve {
+    // Maps 0 -> 0 and 1 -> 1 on the magnitude, keeping the sign for bipolar floats
+    pub fn apply(&self, value: f32) -> f32 {
+        let magnitude = value.abs().min(1.0);
+        let k = SEND_CURVE_STEEPNESS;
+        let shaped = match self {
+            SendCurve::Linear => return value,
+            SendCurve::Exponential => ((k * magnitude).exp() - 1.0) / (k.exp() - 1.0),
+            SendCurve::Logarithmic => (1.0 + (k.exp() - 1.0) * magnitude).ln() / k,
+        };
+        value.signum() * shaped
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OscConfig {
+    pub target_address: String,
+    pub target_port: u16,
+    pub listen_address: String,
+    pub listen_port: u16,
+    // Emit `osc-decode-errors` every time this many packets have failed to decode
+    #[serde(default = "default_decode_error_threshold")]
+    pub decode_error_threshold: u64,
+    // How long stop_listener waits for the listener thread before detaching it
+    #[serde(default = "default_stop_timeout_ms")]
+    pub stop_timeout_ms: u64,
+    // Artificial network conditions for testing; ignored in release builds
+    #[serde(default)]
+    pub debug_network: Option<DebugNetworkConfig>,
+    // Rate at which queued sends are drained
+    #[serde(default = "default_send_rate_hz")]
+    pub send_rate_hz: u32,
+    // Minimum gap between `parameter-updated` events from the listener (0 = every packet)
+    #[serde(default)]
+    pub event_debounce_ms: u64,
+    // Silences the self-send warning for deliberate loopback testing
+    #[serde(default)]
+    pub allow_loopback: bool,
+    // Local address outbound sockets bind to; pick an interface IP on multi-homed machines
+    #[serde(default = "default_send_bind_address")]
+    pub send_bind_address: String,
+    // Values above this become `true` when a float is sent to a Bool parameter
+    #[serde(default = "default_bool_threshold")]
+    pub bool_threshold: f32,
+    // Inbound filters: when accept_patterns is non-empty only matching names are stored;
+    // ignore_patterns always wins. Patterns use `*` wildcards, otherwise they match as substrings
+    #[serde(default)]
+    pub accept_patterns: Vec<String>,
+    #[serde(default)]
+    pub ignore_patterns: Vec<String>,
+    // Key the parameter map case-insensitively so `GestureLeft` and `gestureleft` share one entry.
+    // Off by default: VRChat names are case-sensitive, and with this on two genuinely distinct
+    // parameters differing only in case collapse into one (the first-seen spelling is kept)
+    #[serde(default)]
+    pub case_insensitive_names: bool,
+    // Retry queued sends that fail with a network error, with backoff, before giving up
+    #[serde(default)]
+    pub retry_failed_sends: bool,
+    // Inbound float changes no larger than this are ignored (0 = every change). Ints and bools
+    // always update. The stored value only moves once the change clears the deadband
+    #[serde(default)]
+    pub float_deadband: f32,
+    // Names flagged as meta parameters and listed by get_meta_parameters
+    #[serde(default = "default_meta_parameters")]
+    pub meta_parameters: Vec<String>,
+    // Write persisted files as compact instead of pretty JSON
+    #[serde(default)]
+    pub compact_json: bool,
+    // Largest encoded bundle sent in one packet; bigger bundles are split
+    #[serde(default = "default_max_packet_size")]
+    pub max_packet_size: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DebugNetworkConfig {
+    pub send_delay_ms: u64,
+    pub recv_delay_ms: u64,
+    // Chance (0.0 - 1.0) that a sent or received packet is dropped
+    pub drop_probability: f32,
+}
+
+impl DebugNetworkConfig {
+    fn should_drop(&self) -> bool {
+        self.drop_probability > 0.0 && rand::random::<f32>() < self.drop_probability
+    }
+}
+
+impl OscConfig {
+    // Whether an inbound parameter passes the accept/ignore filters
+    pub fn accepts_parameter(&self, name: &str) -> bool {
+        if self.ignore_patterns.iter().any(|p| pattern_matches(p, name)) {
+            return false;
+        }
+        self.accept_patterns.is_empty() || self.accept_patterns.iter().any(|p| pattern_matches(p, name))
+    }
+
+    // True when we would send to our own listener: same port, and the target is the listen
+    // address, or the listener is bound to all interfaces and the target is a local address.
+    // Every sent parameter then echoes straight back in as an inbound update.
+    pub fn is_self_send(&self) -> bool {
+        if self.target_port != self.listen_port {
+            return false;
         }
         
-        let config = self.config.lock().unwrap().clone();
-        let listen_addr = format!("{}:{}", config.listen_address, config.listen_port);
-        let socket_addr = SocketAddr::from_str(&listen_addr)?;
-        let socket = UdpSocket::bind(socket_addr)?;
-        socket.set_nonblocking(true)?;
+        let target = std::net::IpAddr::from_str(&self.target_address);
+        let listen = std::net::IpAddr::from_str(&self.listen_address);
+        match (target, listen) {
+            (Ok(target), Ok(listen)) => {
+                target == listen || (listen.is_unspecified() && (target.is_loopback() || target.is_unspecified()))
+            }
+            _ => self.target_address == self.listen_address,
+        }
+    }
+
+    // Check that both endpoints are usable socket addresses
+    pub fn validate(&self) -> Result<()> {
+        let target = format!("{}:{}", self.target_address, self.target_port);
+        SocketAddr::from_str(&target).map_err(|e| anyhow!("Invalid target address {}: {}", target, e))?;
         
-        log::info!("OSC listener started on {}", listen_addr);
+        let listen = format!("{}:{}", self.listen_address, self.listen_port);
+        SocketAddr::from_str(&listen).map_err(|e| anyhow!("Invalid listen address {}: {}", listen, e))?;
         
-        let params = self.parameters.clone();
-        let running_ref = self.running.clone();
-        let app_handle_ref = self.app_handle.clone();
+        SocketAddr::from_str(&self.send_bind_address)
+            .map_err(|e| anyhow!("Invalid send bind address {}: {}", self.send_bind_address, e))?;
         
-        *running = true;
+        if !self.bool_threshold.is_finite() {
+            return Err(anyhow!("Bool threshold must be a finite number"));
+        }
         
-        // Spawn a thread to listen for OSC messages
-        let handle = thread::spawn(move || {
-            let mut buf = [0u8; 1024];
-            
-            while *running_ref.lock().unwrap() {
-                match socket.recv_from(&mut buf) {
-                    Ok((size, _addr)) => {
-                        if let Ok((_, packet)) = rosc::decoder::decode_udp(&buf[..size]) {
-                            let mut parameter_updated = false;
-                            
-                            // Process the packet and track if parameters were updated
-                            if let Some(param) = process_osc_packet(packet, &params) {
-                                parameter_updated = true;
-                                
-                                // Notify frontend if we have the app handle
-                                if parameter_updated {
-                                    if let Some(app) = app_handle_ref.lock().unwrap().as_ref() {
-                                        let params_clone = {
-                                            let params_lock = params.lock().unwrap();
-                                            params_lock.values().cloned().collect::<Vec<_>>()
-                                        };
-                                        
-                                        // Try to emit the updated parameters, but don't block if it fails
-                                        let _ = app.emit_all("parameter-updated", params_clone);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                        thread::sleep(Duration::from_millis(10));
-                    }
-                    Err(e) => {
-                        log::error!("Error receiving OSC: {}", e);
-                        thread::sleep(Duration::from_millis(100));
-                    }
-                }
-            }
-        });
+        if !self.float_deadband.is_finite() || self.float_deadband < 0.0 {
+            return Err(anyhow!("Float deadband must be a non-negative number"));
+        }
         
-        let mut thread_ref = self.listener_thread.lock().unwrap();
-        *thread_ref = Some(handle);
+        if self.target_port == 0 {
+            return Err(anyhow!("Target port must not be 0"));
+        }
         
-        Ok(())
-    }
-    
-    // Stop OSC listener
-    pub fn stop_listener(&self) -> Result<()> {
-        let mut running = self.running.lock().unwrap();
-        if !*running {
-            return Ok(());  // Not running
+        if !(MIN_SEND_RATE_HZ..=MAX_SEND_RATE_HZ).contains(&self.send_rate_hz) {
+            return Err(anyhow!(
+                "Send rate must be between {} and {} Hz",
+                MIN_SEND_RATE_HZ,
+                MAX_SEND_RATE_HZ
+            ));
         }
         
-        // Signal thread to stop
-        *running = false;
+        if !(MIN_MAX_PACKET_SIZE..=MAX_MAX_PACKET_SIZE).contains(&self.max_packet_size) {
+            return Err(anyhow!(
+                "Max packet size must be between {} and {} bytes",
+                MIN_MAX_PACKET_SIZE,
+                MAX_MAX_PACKET_SIZE
+            ));
+        }
         
-        // Join thread
-        if let Some(handle) = self.listener_thread.lock().unwrap().take() {
-            // Give the thread a bit of time to exit cleanly
-            if handle.join().is_err() {
-                log::warn!("OSC listener thread did not exit cleanly");
+        if self.event_debounce_ms > MAX_EVENT_DEBOUNCE_MS {
+            return Err(anyhow!("Event debounce must be at most {} ms", MAX_EVENT_DEBOUNCE_MS));
+        }
+        
+        if self.accept_patterns.iter().chain(&self.ignore_patterns).any(|p| p.trim().is_empty()) {
+            return Err(anyhow!("Parameter filter patterns must not be empty"));
+        }
+        
+        if let Some(debug_network) = &self.debug_network {
+            if !(0.0..=1.0).contains(&debug_network.drop_probability) {
+                return Err(anyhow!("Drop probability must be between 0 and 1"));
             }
         }
         
@@ -225,145 +731,4522 @@ impl OscState {
     }
 }
 
-// Process incoming OSC packet
-fn process_osc_packet(packet: OscPacket, params: &Arc<Mutex<HashMap<String, Parameter>>>) -> Option<Parameter> {
-    match packet {
-        OscPacket::Message(msg) => {
-            process_osc_message(msg, params)
-        }
-        OscPacket::Bundle(bundle) => {
-            let mut updated_param = None;
-            for packet in bundle.content {
-                if let Some(param) = process_osc_packet(packet, params) {
-                    updated_param = Some(param);
-                }
-            }
-            updated_param
-        }
-    }
+fn default_decode_error_threshold() -> u64 {
+    DEFAULT_DECODE_ERROR_THRESHOLD
 }
 
-// Process OSC message and extract parameter data
-fn process_osc_message(msg: OscMessage, params: &Arc<Mutex<HashMap<String, Parameter>>>) -> Option<Parameter> {
-    // Only process avatar parameter messages
-    if msg.addr.starts_with("/avatar/parameters/") {
-        let param_name = msg.addr.trim_start_matches("/avatar/parameters/").to_string();
+fn default_stop_timeout_ms() -> u64 {
+    DEFAULT_STOP_TIMEOUT_MS
+}
 
-        if let Some(value) = msg.args.first() {
-            let (value, param_type) = match value {
-                OscType::Float(f) => (*f, ParameterType::Float),
-                OscType::Int(i) => (*i as f32, ParameterType::Int),
-                OscType::Bool(b) => {
-                    if *b {
-                        (1.0, ParameterType::Bool)
-                    } else {
-                        (0.0, ParameterType::Bool)
-                    }
-                }
-                _ => return None, // Unsupported type
-            };
+fn default_bool_threshold() -> f32 {
+    DEFAULT_BOOL_THRESHOLD
+}
 
-            let param = Parameter {
-                name: param_name,
-                parameter_type: param_type,
-                value,
-            };
-            
-            let mut params_map = params.lock().unwrap();
-            params_map.insert(param.name.clone(), param.clone());
-            
-            return Some(param);
-        }
+fn default_max_packet_size() -> usize {
+    DEFAULT_MAX_PACKET_SIZE
+}
+
+// Map key for a parameter name: lowercased in case-insensitive mode, otherwise unchanged
+fn name_key(name: &str, case_insensitive: bool) -> String {
+    if case_insensitive {
+        name.to_lowercase()
+    } else {
+        name.to_string()
     }
-    
-    None
 }
 
-// Send OSC message to VRChat
-pub fn send_parameter(param_name: &str, value: f32, param_type: &ParameterType, osc_state: &OscState) -> Result<()> {
-    let config = osc_state.get_config();
-    let addr = format!("/avatar/parameters/{}", param_name);
-    
-    let arg = match param_type {
-        ParameterType::Float => OscType::Float(value),
-        ParameterType::Int => OscType::Int(value as i32),
-        ParameterType::Bool => OscType::Bool(value > 0.5),
-    };
+// Re-key every entry of a per-parameter settings map
+fn rekey_map<V>(map: &Mutex<HashMap<String, V>>, rekey: &impl Fn(String) -> String) {
+    let mut map = map.lock().unwrap();
+    let old = std::mem::take(&mut *map);
+    map.extend(old.into_iter().map(|(name, value)| (rekey(name), value)));
+}
+
+fn remove_keys<V>(map: &Mutex<HashMap<String, V>>, keys: &HashSet<String>) {
+    map.lock().unwrap().retain(|name, _| !keys.contains(name));
+}
+
+// `*` matches any run of characters; a pattern without `*` matches as a substring
+fn pattern_matches(pattern: &str, name: &str) -> bool {
+    if !pattern.contains('*') {
+        return name.contains(pattern);
+    }
     
-    let msg = OscMessage {
-        addr,
-        args: vec![arg],
-    };
+    let segments: Vec<&str> = pattern.split('*').collect();
+    let (first, last) = (segments[0], segments[segments.len() - 1]);
+    if !name.starts_with(first) || name.len() < first.len() + last.len() || !name.ends_with(last) {
+        return false;
+    }
     
-    let packet = OscPacket::Message(msg);
-    let dest_addr = format!("{}:{}", config.target_address, config.target_port);
-    let dest_socket_addr = SocketAddr::from_str(&dest_addr)?;
+    // Middle segments must appear in order between the anchored prefix and suffix
+    let mut rest = &name[first.len()..name.len() - last.len()];
+    for segment in &segments[1..segments.len() - 1] {
+        match rest.find(segment) {
+            Some(index) => rest = &rest[index + segment.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+// Case-insensitive subsequence match of `query` in `name`. Consecutive characters and matches at
+// the start of a word (after `/`, `_`, `-`, `.` or a lowercase-to-uppercase step) score higher
+fn fuzzy_score(query: &str, name: &str) -> Option<(u32, Vec<usize>)> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut score = 0;
+    let mut matched = Vec::new();
+    let mut next = 0;
     
-    let socket = UdpSocket::bind("0.0.0.0:0")?;
-    let encoded = rosc::encoder::encode(&packet)?;
-    socket.send_to(&encoded, dest_socket_addr)?;
+    for q in query.chars().flat_map(char::to_lowercase) {
+        let index = (next..chars.len()).find(|&i| chars[i].to_lowercase().eq(std::iter::once(q)))?;
+        
+        score += 1;
+        if matched.last().map_or(false, |&last| last + 1 == index) {
+            score += 5;
+        }
+        let word_start = index == 0
+            || matches!(chars[index - 1], '/' | '_' | '-' | '.')
+            || (chars[index - 1].is_lowercase() && chars[index].is_uppercase());
+        if word_start {
+            score += 3;
+        }
+        
+        matched.push(index);
+        next = index + 1;
+    }
     
-    Ok(())
+    Some((score, matched))
 }
 
-// Tauri commands
+fn default_meta_parameters() -> Vec<String> {
+    DEFAULT_META_PARAMETERS.iter().map(|name| name.to_string()).collect()
+}
 
-#[tauri::command]
-pub fn get_all_parameters(state: State<OscState>) -> Vec<Parameter> {
-    state.get_parameters()
+fn default_send_bind_address() -> String {
+    DEFAULT_SEND_BIND_ADDRESS.to_string()
 }
 
-#[tauri::command]
-pub fn set_parameter_value(
-    name: &str,
-    value: f32,
-    param_type_str: &str,
-    state: State<OscState>,
-) -> Result<(), String> {
-    let param_type = match param_type_str {
-        "Float" => ParameterType::Float,
-        "Int" => ParameterType::Int,
-        "Bool" => ParameterType::Bool,
-        _ => return Err("Invalid parameter type".into()),
-    };
-    
-    send_parameter(name, value, &param_type, &state)
-        .map_err(|e| format!("Failed to send parameter: {}", e))?;
-    
-    state.set_parameter(name, value).map_err(|e| format!("Failed to update parameter: {}", e))?;
-    
-    Ok(())
+fn default_send_rate_hz() -> u32 {
+    DEFAULT_SEND_RATE_HZ
 }
 
-#[tauri::command]
-pub fn update_osc_config(
-    target_address: &str,
-    target_port: u16,
-    listen_address: &str,
-    listen_port: u16,
-    state: State<OscState>,
-) -> Result<(), String> {
-    let config = OscConfig {
-        target_address: target_address.to_string(),
-        target_port,
-        listen_address: listen_address.to_string(),
-        listen_port,
-    };
-    
-    state.update_config(config)
-        .map_err(|e| format!("Failed to update OSC config: {}", e))
+impl Default for OscConfig {
+    fn default() -> Self {
+        Self {
+            target_address: "127.0.0.1".to_string(),
+            target_port: 9000,
+            listen_address: "127.0.0.1".to_string(),
+            listen_port: 9001,
+            decode_error_threshold: DEFAULT_DECODE_ERROR_THRESHOLD,
+            stop_timeout_ms: DEFAULT_STOP_TIMEOUT_MS,
+            debug_network: None,
+            send_rate_hz: DEFAULT_SEND_RATE_HZ,
+            event_debounce_ms: 0,
+            allow_loopback: false,
+            send_bind_address: DEFAULT_SEND_BIND_ADDRESS.to_string(),
+            bool_threshold: DEFAULT_BOOL_THRESHOLD,
+            accept_patterns: Vec::new(),
+            ignore_patterns: Vec::new(),
+            case_insensitive_names: false,
+            retry_failed_sends: false,
+            float_deadband: 0.0,
+            meta_parameters: default_meta_parameters(),
+            compact_json: false,
+            max_packet_size: DEFAULT_MAX_PACKET_SIZE,
+        }
+    }
 }
 
-#[tauri::command]
-pub fn get_osc_config(state: State<OscState>) -> OscConfig {
-    state.get_config()
+// What the listener does after a receive error
+#[derive(Debug, PartialEq)]
+enum RecvErrorAction {
+    // Wait, then receive again on the same socket
+    Retry(Duration),
+    // Wait, then replace the socket with a fresh bind
+    Rebind(Duration),
 }
 
-#[tauri::command]
-pub fn restart_osc_listener(state: State<OscState>) -> Result<(), String> {
-    state.stop_listener()
-        .map_err(|e| format!("Failed to stop OSC listener: {}", e))?;
-    
-    state.start_listener()
-        .map_err(|e| format!("Failed to start OSC listener: {}", e))
-} 
\ No newline at end of file
+// Backoff between listener receive errors. The wait doubles up to a cap, and repeated or
+// non-transient failures ask for a rebind
+#[derive(Debug)]
+struct ListenerBackoff {
+    delay: Duration,
+    consecutive_errors: u32,
+}
+
+impl ListenerBackoff {
+    fn new() -> Self {
+        Self {
+            delay: Duration::from_millis(LISTENER_BACKOFF_MIN_MS),
+            consecutive_errors: 0,
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn on_error(&mut self, error: &std::io::Error) -> RecvErrorAction {
+        self.consecutive_errors += 1;
+        let delay = self.delay;
+        
+        if is_transient_error(error) && self.consecutive_errors < LISTENER_MAX_CONSECUTIVE_ERRORS {
+            self.delay = (self.delay * 2).min(Duration::from_millis(LISTENER_BACKOFF_MAX_MS));
+            RecvErrorAction::Retry(delay)
+        } else {
+            RecvErrorAction::Rebind(delay)
+        }
+    }
+}
+
+// A spawned listener thread and the flag that stops it. Each thread gets its own flag, so one
+// that was detached after a stop timeout can't pick up the state of a later restart
+#[derive(Debug)]
+struct ListenerThread {
+    handle: thread::JoinHandle<()>,
+    stop: Arc<AtomicBool>,
+}
+
+// Outbound bytes per one-second window, tracked lock-free so sends never contend on it
+#[derive(Debug, Default)]
+struct SendBandwidth {
+    window_sec: AtomicU64,
+    window_bytes: AtomicU64,
+    last_rate: AtomicU64,
+    peak_rate: AtomicU64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BandwidthStats {
+    // Bytes sent during the last complete second
+    pub current_bytes_per_sec: u64,
+    pub peak_bytes_per_sec: u64,
+}
+
+fn unix_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn unix_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+impl SendBandwidth {
+    fn record(&self, bytes: u64) {
+        let now = unix_secs();
+        let window = self.window_sec.load(Ordering::Relaxed);
+        
+        // The thread that wins the rollover closes out the previous window
+        if now != window && self.window_sec.compare_exchange(window, now, Ordering::Relaxed, Ordering::Relaxed).is_ok() {
+            let completed = self.window_bytes.swap(bytes, Ordering::Relaxed);
+            let rate = if now == window + 1 { completed } else { 0 };
+            self.last_rate.store(rate, Ordering::Relaxed);
+            self.peak_rate.fetch_max(completed, Ordering::Relaxed);
+            return;
+        }
+        self.window_bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    fn stats(&self) -> BandwidthStats {
+        let now = unix_secs();
+        let window = self.window_sec.load(Ordering::Relaxed);
+        let current = if now == window {
+            self.last_rate.load(Ordering::Relaxed)
+        } else if now == window + 1 {
+            self.window_bytes.load(Ordering::Relaxed)
+        } else {
+            0 // Nothing sent for over a second
+        };
+        
+        BandwidthStats {
+            current_bytes_per_sec: current,
+            peak_bytes_per_sec: self.peak_rate.load(Ordering::Relaxed).max(current),
+        }
+    }
+
+    fn reset(&self) {
+        self.window_sec.store(0, Ordering::Relaxed);
+        self.window_bytes.store(0, Ordering::Relaxed);
+        self.last_rate.store(0, Ordering::Relaxed);
+        self.peak_rate.store(0, Ordering::Relaxed);
+    }
+}
+
+// Inbound updates per parameter over one-second windows; two fields so the receive loop
+// only pays for a map lookup and an increment
+#[derive(Debug, Clone)]
+struct UpdateRate {
+    window_start: Instant,
+    count: u32,
+    last_rate: u32,
+}
+
+impl UpdateRate {
+    const WINDOW: Duration = Duration::from_secs(1);
+
+    fn new(now: Instant) -> Self {
+        Self { window_start: now, count: 0, last_rate: 0 }
+    }
+
+    fn record(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.window_start);
+        if elapsed >= Self::WINDOW {
+            // A gap longer than one window means the previous second saw nothing
+            self.last_rate = if elapsed < Self::WINDOW * 2 { self.count } else { 0 };
+            self.window_start = now;
+            self.count = 0;
+        }
+        self.count += 1;
+    }
+
+    fn rate(&self, now: Instant) -> u32 {
+        match now.duration_since(self.window_start) {
+            elapsed if elapsed < Self::WINDOW => self.last_rate,
+            elapsed if elapsed < Self::WINDOW * 2 => self.count,
+            _ => 0,
+        }
+    }
+}
+
+// Callback run on the listener thread for every decoded inbound message
+pub type MessageCallback = Arc<dyn Fn(&OscMessage) + Send + Sync>;
+
+#[derive(Clone, Default)]
+struct MessageCallbacks(Vec<MessageCallback>);
+
+impl std::fmt::Debug for MessageCallbacks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MessageCallbacks({})", self.0.len())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct OscState {
+    parameters: Arc<Mutex<HashMap<String, Parameter>>>,
+    labels: Arc<Mutex<HashMap<String, String>>>,
+    curves: Arc<Mutex<HashMap<String, ResponseCurve>>>,
+    send_curves: Arc<Mutex<HashMap<String, SendCurve>>>,
+    writable_overrides: Arc<Mutex<HashMap<String, bool>>>,
+    watches: Arc<Mutex<Vec<ParameterWatch>>>,
+    queue_modes: Arc<Mutex<HashMap<String, QueueMode>>>,
+    avatar_config: Arc<Mutex<Option<AvatarConfig>>>,
+    send_debug_watch: Arc<Mutex<Option<String>>>,
+    // Windows (by label) that only want some parameters; every other window gets the full list
+    update_subscriptions: Arc<Mutex<HashMap<String, UpdateSubscription>>>,
+    // One-shot listeners waiting for the next inbound value of a parameter
+    change_waiters: Arc<Mutex<Vec<(String, oneshot::Sender<Parameter>)>>>,
+    message_callbacks: Arc<Mutex<MessageCallbacks>>,
+    send_bandwidth: Arc<SendBandwidth>,
+    update_rates: Arc<Mutex<HashMap<String, UpdateRate>>>,
+    observed_addresses: Arc<Mutex<HashMap<String, ObservedAddress>>>,
+    // Outbound socket shared by all sends, with the address it is bound to
+    send_socket: Arc<Mutex<Option<(String, UdpSocket)>>>,
+    send_queue: Arc<(Mutex<VecDeque<QueuedSend>>, Condvar)>,
+    retry_queue: Arc<Mutex<VecDeque<RetrySend>>>,
+    send_burst: Arc<Mutex<Option<SendBurst>>>,
+    next_burst_id: Arc<AtomicU64>,
+    config: Arc<Mutex<OscConfig>>,
+    listener_thread: Arc<Mutex<Option<ListenerThread>>>,
+    // Port of the listener lock file we hold, so it is released even if the config changed since
+    locked_port: Arc<Mutex<Option<u16>>>,
+    running: Arc<Mutex<bool>>,
+    bound_address: Arc<Mutex<Option<SocketAddr>>>,
+    paused: Arc<AtomicBool>,
+    decode_errors: Arc<AtomicU64>,
+    packets_received: Arc<AtomicU64>,
+    // monotonic_ms() of the last inbound packet plus one; 0 until a packet arrives
+    last_packet_ms: Arc<AtomicU64>,
+    emit_failures: Arc<AtomicU64>,
+    filtered_messages: Arc<AtomicU64>,
+    unsupported_messages: Arc<AtomicU64>,
+    app_handle: Arc<Mutex<Option<AppHandle>>>,
+    // Per-instance parameters directory, resolved once the app handle is known
+    data_dir: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl OscState {
+    pub fn new() -> Self {
+        Self {
+            parameters: Arc::new(Mutex::new(HashMap::new())),
+            labels: Arc::new(Mutex::new(HashMap::new())),
+            curves: Arc::new(Mutex::new(HashMap::new())),
+            send_curves: Arc::new(Mutex::new(HashMap::new())),
+            writable_overrides: Arc::new(Mutex::new(HashMap::new())),
+            watches: Arc::new(Mutex::new(Vec::new())),
+            queue_modes: Arc::new(Mutex::new(HashMap::new())),
+            avatar_config: Arc::new(Mutex::new(None)),
+            send_debug_watch: Arc::new(Mutex::new(None)),
+            update_subscriptions: Arc::new(Mutex::new(HashMap::new())),
+            change_waiters: Arc::new(Mutex::new(Vec::new())),
+            message_callbacks: Arc::new(Mutex::new(MessageCallbacks::default())),
+            send_bandwidth: Arc::new(SendBandwidth::default()),
+            update_rates: Arc::new(Mutex::new(HashMap::new())),
+            observed_addresses: Arc::new(Mutex::new(HashMap::new())),
+            send_socket: Arc::new(Mutex::new(None)),
+            send_queue: Arc::new((Mutex::new(VecDeque::new()), Condvar::new())),
+            retry_queue: Arc::new(Mutex::new(VecDeque::new())),
+            send_burst: Arc::new(Mutex::new(None)),
+            next_burst_id: Arc::new(AtomicU64::new(1)),
+            config: Arc::new(Mutex::new(OscConfig::default())),
+            listener_thread: Arc::new(Mutex::new(None)),
+            locked_port: Arc::new(Mutex::new(None)),
+            running: Arc::new(Mutex::new(false)),
+            bound_address: Arc::new(Mutex::new(None)),
+            paused: Arc::new(AtomicBool::new(false)),
+            decode_errors: Arc::new(AtomicU64::new(0)),
+            packets_received: Arc::new(AtomicU64::new(0)),
+            last_packet_ms: Arc::new(AtomicU64::new(0)),
+            emit_failures: Arc::new(AtomicU64::new(0)),
+            filtered_messages: Arc::new(AtomicU64::new(0)),
+            unsupported_messages: Arc::new(AtomicU64::new(0)),
+            app_handle: Arc::new(Mutex::new(None)),
+            data_dir: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn initialize(&self, app_handle: AppHandle) {
+        *self.data_dir.lock().unwrap() = app_handle
+            .path()
+            .app_data_dir()
+            .ok()
+            .map(|app_data| instance_data_dir(app_data, instance_id()).join("parameters"));
+        let mut app_handle_ref = self.app_handle.lock().unwrap();
+        *app_handle_ref = Some(app_handle);
+        drop(app_handle_ref); // Release the lock before resolving data paths
+        
+        if let Err(e) = self.load_config() {
+            log::error!("Failed to load OSC config: {}", e);
+        }
+        for e in self.load_parameter_settings() {
+            log::error!("{}", e);
+        }
+        
+        self.spawn_workers();
+    }
+
+    // Background threads for the send queue, send retries and the listener watchdog
+    pub(crate) fn spawn_workers(&self) {
+        let state = self.clone();
+        thread::spawn(move || state.run_send_worker());
+        
+        let state = self.clone();
+        thread::spawn(move || state.run_retry_worker());
+        
+        let state = self.clone();
+        thread::spawn(move || state.run_listener_watchdog());
+    }
+
+    // Load every per-parameter settings file, returning the failures
+    fn load_parameter_settings(&self) -> Vec<String> {
+        [
+            ("parameter labels", self.load_labels()),
+            ("parameter curves", self.load_curves()),
+            ("parameter send curves", self.load_send_curves()),
+            ("parameter access overrides", self.load_writable_overrides()),
+            ("parameter watches", self.load_watches()),
+            ("parameter queue modes", self.load_queue_modes()),
+            ("avatar config", self.load_avatar_config()),
+        ]
+        .into_iter()
+        .filter_map(|(what, result)| result.err().map(|e| format!("Failed to load {}: {}", what, e)))
+        .collect()
+    }
+
+    // Re-read config and parameter settings after the files were changed outside the app. The
+    // config goes through update_config, so a changed listen address restarts the listener
+    pub fn reload_from_disk(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        
+        match self.load_data::<OscConfig>("config.json") {
+            Ok(Some(config)) => {
+                if let Err(e) = self.update_config(config) {
+                    errors.push(format!("Failed to apply OSC config: {}", e));
+                }
+            }
+            Ok(None) => {}
+            Err(e) => errors.push(format!("Failed to load OSC config: {}", e)),
+        }
+        errors.extend(self.load_parameter_settings());
+        
+        self.emit_parameters();
+        errors
+    }
+
+    // Shared app data directory, where listener locks live regardless of instance
+    fn shared_data_dir(&self) -> Option<PathBuf> {
+        self.app_handle
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|app_handle| app_handle.path().app_data_dir().ok())
+    }
+
+    // Refuse a port another live VRCParam process is listening on, with a clearer message than
+    // the bind error; otherwise take the lock for it once bound
+    fn check_listener_lock(&self, port: u16) -> Result<()> {
+        let Some(app_data) = self.shared_data_dir() else {
+            return Ok(());
+        };
+        
+        if let Some(other) = listener_lock::find_conflict(&app_data, port) {
+            self.emit_event("osc-port-conflict", other.clone());
+            return Err(anyhow!(
+                "Port {} is already used by another VRCParam instance (PID {})",
+                port,
+                other.pid
+            ));
+        }
+        Ok(())
+    }
+
+    fn acquire_listener_lock(&self, port: u16) {
+        let Some(app_data) = self.shared_data_dir() else {
+            return;
+        };
+        match listener_lock::acquire(&app_data, port) {
+            Ok(()) => *self.locked_port.lock().unwrap() = Some(port),
+            Err(e) => log::warn!("{}", e),
+        }
+    }
+
+    fn release_listener_lock(&self) {
+        if let (Some(port), Some(app_data)) = (self.locked_port.lock().unwrap().take(), self.shared_data_dir()) {
+            listener_lock::release(&app_data, port);
+        }
+    }
+
+    fn get_data_path(&self, file_name: &str) -> Result<PathBuf, String> {
+        let dir_path = self
+            .data_dir
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or("App data directory not resolved")?;
+        
+        // Ensure the directory exists
+        if !dir_path.exists() {
+            fs::create_dir_all(&dir_path).map_err(|e| format!("Failed to create parameters directory: {}", e))?;
+        }
+        
+        Ok(dir_path.join(file_name))
+    }
+
+    // Write a JSON file into the parameters data directory
+    fn save_data<T: Serialize>(&self, file_name: &str, data: &T) -> Result<(), String> {
+        let path = self.get_data_path(file_name)?;
+        let json = to_versioned_json(data)
+            .map_err(|e| format!("Failed to serialize {}: {}", file_name, e))?;
+        
+        fs::write(path, json).map_err(|e| format!("Failed to write {} to disk: {}", file_name, e))?;
+        Ok(())
+    }
+
+    // Read a JSON file from the parameters data directory, None if it doesn't exist yet
+    fn load_data<T: DeserializeOwned>(&self, file_name: &str) -> Result<Option<T>, String> {
+        let path = self.get_data_path(file_name)?;
+        
+        if !path.exists() {
+            return Ok(None);
+        }
+        
+        let json = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", file_name, e))?;
+        let data = from_versioned_json(&json, no_migrations)
+            .map_err(|e| format!("Failed to parse {}: {}", file_name, e))?;
+        
+        Ok(Some(data))
+    }
+
+    pub fn save_config(&self) -> Result<(), String> {
+        let config = self.get_config();
+        self.save_data("config.json", &config)
+    }
+
+    pub fn load_config(&self) -> Result<(), String> {
+        if let Some(loaded_config) = self.load_data::<OscConfig>("config.json")? {
+            loaded_config.validate().map_err(|e| e.to_string())?;
+            set_compact_json(loaded_config.compact_json);
+            *self.config.lock().unwrap() = loaded_config;
+        }
+        Ok(())
+    }
+
+    // Write the full config to a user-chosen file, in the same format as config.json
+    pub fn export_config(&self, path: &str) -> Result<(), String> {
+        let json = to_versioned_json(&self.get_config())
+            .map_err(|e| format!("Failed to serialize config: {}", e))?;
+        fs::write(path, json).map_err(|e| format!("Failed to write config file: {}", e))
+    }
+
+    // Read a config exported by export_config and apply it through update_config
+    pub fn import_config(&self, path: &str) -> Result<(), String> {
+        let json = fs::read_to_string(path).map_err(|e| format!("Failed to read config file: {}", e))?;
+        let config: OscConfig = from_versioned_json(&json, no_migrations)
+            .map_err(|e| format!("Failed to parse config file: {}", e))?;
+        
+        self.update_config(config)
+            .map_err(|e| format!("Failed to apply imported config: {}", e))
+    }
+
+    pub fn save_labels(&self) -> Result<(), String> {
+        let labels = self.labels.lock().unwrap().clone();
+        self.save_data("labels.json", &labels)
+    }
+
+    pub fn load_labels(&self) -> Result<(), String> {
+        let loaded_labels: HashMap<String, String> = match self.load_data("labels.json")? {
+            Some(labels) => labels,
+            None => return Ok(()), // No labels file yet, that's fine
+        };
+        
+        let loaded_labels = self.keyed(loaded_labels);
+        
+        // Apply labels to any parameters we already know about
+        let mut params = self.parameters.lock().unwrap();
+        for (key, param) in params.iter_mut() {
+            param.display_name = loaded_labels.get(key).cloned();
+        }
+        
+        let mut labels = self.labels.lock().unwrap();
+        *labels = loaded_labels;
+        
+        Ok(())
+    }
+
+    pub fn save_curves(&self) -> Result<(), String> {
+        let curves = self.curves.lock().unwrap().clone();
+        self.save_data("curves.json", &curves)
+    }
+
+    pub fn load_curves(&self) -> Result<(), String> {
+        let loaded_curves: HashMap<String, ResponseCurve> = match self.load_data("curves.json")? {
+            Some(curves) => curves,
+            None => return Ok(()), // No curves file yet, that's fine
+        };
+        
+        let loaded_curves = self.keyed(loaded_curves);
+        
+        // Reshape any parameters we already know about
+        let mut params = self.parameters.lock().unwrap();
+        for (key, param) in params.iter_mut() {
+            param.shaped_value = loaded_curves.get(key).map(|curve| curve.apply(param.value));
+        }
+        
+        let mut curves = self.curves.lock().unwrap();
+        *curves = loaded_curves;
+        
+        Ok(())
+    }
+
+    // Attach or clear the response curve used to shape a parameter's inbound value
+    pub fn set_parameter_curve(&self, name: &str, curve: Option<ResponseCurve>) -> Result<(), String> {
+        if let Some(curve) = &curve {
+            curve.validate()?;
+        }
+        
+        let key = self.parameter_key(name);
+        let mut curves = self.curves.lock().unwrap();
+        match &curve {
+            Some(curve) => {
+                curves.insert(key.clone(), curve.clone());
+            }
+            None => {
+                curves.remove(&key);
+            }
+        }
+        drop(curves); // Release the lock before saving
+        
+        if let Some(param) = self.parameters.lock().unwrap().get_mut(&key) {
+            param.shaped_value = curve.map(|curve| curve.apply(param.value));
+        }
+        
+        self.save_curves()?;
+        self.emit_parameters();
+        
+        Ok(())
+    }
+
+    pub fn save_send_curves(&self) -> Result<(), String> {
+        let curves = self.send_curves.lock().unwrap().clone();
+        self.save_data("send_curves.json", &curves)
+    }
+
+    pub fn load_send_curves(&self) -> Result<(), String> {
+        if let Some(loaded_curves) = self.load_data::<HashMap<String, SendCurve>>("send_curves.json")? {
+            *self.send_curves.lock().unwrap() = self.keyed(loaded_curves);
+        }
+        Ok(())
+    }
+
+    // Attach or clear the curve applied to a parameter's outgoing Float values
+    pub fn set_parameter_send_curve(&self, name: &str, curve: Option<SendCurve>) -> Result<(), String> {
+        let key = self.parameter_key(name);
+        let mut curves = self.send_curves.lock().unwrap();
+        match curve {
+            Some(curve) => {
+                curves.insert(key, curve);
+            }
+            None => {
+                curves.remove(&key);
+            }
+        }
+        drop(curves); // Release the lock before saving
+        self.save_send_curves()
+    }
+
+    // The value actually sent for a UI value; only Float parameters are shaped
+    fn outbound_value(&self, name: &str, value: f32, param_type: &ParameterType) -> f32 {
+        if *param_type != ParameterType::Float {
+            return value;
+        }
+        let key = self.parameter_key(name);
+        self.send_curves.lock().unwrap().get(&key).map_or(value, |curve| curve.apply(value))
+    }
+
+    // Attach or clear a UI-only display label, keyed by the real parameter name
+    pub fn set_display_label(&self, name: &str, label: Option<String>) -> Result<(), String> {
+        let label = label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty());
+        
+        let key = self.parameter_key(name);
+        let mut labels = self.labels.lock().unwrap();
+        match &label {
+            Some(label) => {
+                labels.insert(key.clone(), label.clone());
+            }
+            None => {
+                labels.remove(&key);
+            }
+        }
+        drop(labels); // Release the lock before saving
+        
+        if let Some(param) = self.parameters.lock().unwrap().get_mut(&key) {
+            param.display_name = label;
+        }
+        
+        self.save_labels()?;
+        self.emit_parameters();
+        
+        Ok(())
+    }
+
+    pub fn save_writable_overrides(&self) -> Result<(), String> {
+        let overrides = self.writable_overrides.lock().unwrap().clone();
+        self.save_data("writable.json", &overrides)
+    }
+
+    pub fn load_writable_overrides(&self) -> Result<(), String> {
+        let loaded_overrides: HashMap<String, bool> = match self.load_data("writable.json")? {
+            Some(overrides) => overrides,
+            None => return Ok(()), // No overrides file yet, that's fine
+        };
+        
+        let loaded_overrides = self.keyed(loaded_overrides);
+        
+        let mut params = self.parameters.lock().unwrap();
+        for (key, writable) in &loaded_overrides {
+            if let Some(param) = params.get_mut(key) {
+                param.writable = *writable;
+            }
+        }
+        
+        *self.writable_overrides.lock().unwrap() = loaded_overrides;
+        Ok(())
+    }
+
+    // Manually mark a parameter as writable or read-only, overriding discovered access
+    pub fn set_parameter_writable(&self, name: &str, writable: bool) -> Result<(), String> {
+        let key = self.parameter_key(name);
+        self.writable_overrides.lock().unwrap().insert(key.clone(), writable);
+        
+        if let Some(param) = self.parameters.lock().unwrap().get_mut(&key) {
+            param.writable = writable;
+        }
+        
+        self.save_writable_overrides()?;
+        self.emit_parameters();
+        
+        Ok(())
+    }
+
+    // Log every outbound send of one parameter at debug level; None turns it off
+    pub fn set_send_debug_watch(&self, name: Option<String>) {
+        *self.send_debug_watch.lock().unwrap() = name;
+    }
+
+    fn log_debug_send(&self, name: &str, args: &[OscType]) {
+        if self.send_debug_watch.lock().unwrap().as_deref() != Some(name) {
+            return;
+        }
+        let config = self.get_config();
+        log::debug!(
+            "Sending {} {:?} to {}:{}",
+            self.parameter_osc_address(name),
+            args,
+            config.target_address,
+            config.target_port
+        );
+    }
+
+    // Reject sends to parameters known to be read-only
+    pub fn ensure_writable(&self, name: &str) -> Result<()> {
+        let writable = match self.get_parameter(name) {
+            Some(param) => param.writable,
+            // Not seen yet: a manual override decides, then the imported avatar config
+            None => {
+                let key = self.parameter_key(name);
+                let overridden = self.writable_overrides.lock().unwrap().get(&key).copied();
+                overridden.unwrap_or_else(|| !self.config_read_only(name))
+            }
+        };
+        
+        if writable {
+            Ok(())
+        } else {
+            Err(anyhow!("Parameter is read-only: {}", name))
+        }
+    }
+
+    pub fn save_watches(&self) -> Result<(), String> {
+        let watches = self.watches.lock().unwrap().clone();
+        self.save_data("watches.json", &watches)
+    }
+
+    pub fn load_watches(&self) -> Result<(), String> {
+        if let Some(loaded_watches) = self.load_data::<Vec<ParameterWatch>>("watches.json")? {
+            *self.watches.lock().unwrap() = loaded_watches;
+        }
+        Ok(())
+    }
+
+    pub fn add_parameter_watch(&self, parameter_name: &str, condition: WatchCondition, notify_os: bool) -> Result<ParameterWatch, String> {
+        let key = self.parameter_key(parameter_name);
+        let mut watches = self.watches.lock().unwrap();
+        let id = watches.iter().map(|w| w.id).max().map_or(1, |id| id + 1);
+        
+        // Start from the current state so an already-true condition doesn't alert immediately
+        let triggered = self
+            .parameters
+            .lock()
+            .unwrap()
+            .get(&key)
+            .map_or(false, |p| condition.matches(p.value));
+        
+        let watch = ParameterWatch {
+            id,
+            parameter_name: parameter_name.to_string(),
+            condition,
+            notify_os,
+            triggered,
+            last_notified: None,
+        };
+        watches.push(watch.clone());
+        
+        drop(watches); // Release the lock before saving
+        self.save_watches()?;
+        
+        Ok(watch)
+    }
+
+    pub fn remove_parameter_watch(&self, id: u64) -> Result<bool, String> {
+        let mut watches = self.watches.lock().unwrap();
+        let initial_len = watches.len();
+        watches.retain(|w| w.id != id);
+        let removed = watches.len() < initial_len;
+        
+        if removed {
+            drop(watches); // Release the lock before saving
+            self.save_watches()?;
+        }
+        
+        Ok(removed)
+    }
+
+    pub fn get_parameter_watches(&self) -> Vec<ParameterWatch> {
+        self.watches.lock().unwrap().clone()
+    }
+
+    // Check watches on an updated parameter, alerting on conditions that just became true
+    fn evaluate_watches(&self, param: &Parameter) {
+        let (alerts, notifications) = self.fire_watches(param);
+        if alerts.is_empty() {
+            return;
+        }
+        
+        for alert in alerts {
+            log::info!("Parameter alert: {} = {} ({:?})", alert.parameter_name, alert.value, alert.condition);
+            self.emit_event("parameter-alert", alert);
+        }
+        
+        if let Some(app_handle) = self.app_handle.lock().unwrap().as_ref() {
+            for alert in notifications {
+                let result = app_handle
+                    .notification()
+                    .builder()
+                    .title("VRCParam alert")
+                    .body(format!("{} is now {}", alert.parameter_name, alert.value))
+                    .show();
+                if let Err(e) = result {
+                    log::warn!("Failed to show desktop notification: {}", e);
+                }
+            }
+        }
+    }
+
+    // Update each matching watch's state, returning the alerts that fired and the subset due a
+    // desktop notification
+    fn fire_watches(&self, param: &Parameter) -> (Vec<ParameterAlert>, Vec<ParameterAlert>) {
+        let mut alerts = Vec::new();
+        let mut notifications = Vec::new();
+        
+        // Watches keep the name as entered, so compare them by key
+        let case_insensitive = self.config.lock().unwrap().case_insensitive_names;
+        let key = name_key(&param.name, case_insensitive);
+        
+        let mut watches = self.watches.lock().unwrap();
+        for watch in watches.iter_mut().filter(|w| name_key(&w.parameter_name, case_insensitive) == key) {
+            let matches = watch.condition.matches(param.value);
+            if matches && !watch.triggered {
+                let alert = ParameterAlert {
+                    watch_id: watch.id,
+                    parameter_name: param.name.clone(),
+                    value: param.value,
+                    condition: watch.condition.clone(),
+                };
+                
+                let notify_due = watch.last_notified.map_or(true, |t| t.elapsed() >= WATCH_NOTIFY_INTERVAL);
+                if watch.notify_os && notify_due {
+                    watch.last_notified = Some(Instant::now());
+                    notifications.push(alert.clone());
+                }
+                alerts.push(alert);
+            }
+            watch.triggered = matches;
+        }
+        
+        (alerts, notifications)
+    }
+
+    pub fn save_queue_modes(&self) -> Result<(), String> {
+        let modes = self.queue_modes.lock().unwrap().clone();
+        self.save_data("queue_modes.json", &modes)
+    }
+
+    pub fn load_avatar_config(&self) -> Result<(), String> {
+        if let Some(loaded_config) = self.load_data::<AvatarConfig>("avatar_config.json")? {
+            *self.avatar_config.lock().unwrap() = Some(loaded_config);
+        }
+        Ok(())
+    }
+
+    // Replace (or clear) the imported avatar config, kept across restarts
+    pub fn set_avatar_config(&self, config: Option<AvatarConfig>) -> Result<(), String> {
+        match &config {
+            Some(config) => self.save_data("avatar_config.json", config)?,
+            None => {
+                let path = self.get_data_path("avatar_config.json")?;
+                if path.exists() {
+                    fs::remove_file(path).map_err(|e| format!("Failed to remove avatar config: {}", e))?;
+                }
+            }
+        }
+        
+        // Re-derive menu paths and custom addresses for parameters we already know about, and
+        // mark the ones the config declares read-only unless manually overridden
+        let menu_paths = config.as_ref().map(AvatarConfig::menu_paths).unwrap_or_default();
+        let custom_addresses = config.as_ref().map(AvatarConfig::custom_addresses).unwrap_or_default();
+        let read_only = config.as_ref().map(AvatarConfig::read_only_parameters).unwrap_or_default();
+        *self.avatar_config.lock().unwrap() = config;
+        let overrides = self.writable_overrides.lock().unwrap().clone();
+        for (key, param) in self.parameters.lock().unwrap().iter_mut() {
+            param.menu_path = menu_paths.get(&param.name).cloned();
+            param.osc_address = custom_addresses.get(&param.name).cloned();
+            if read_only.contains(&param.name) && !overrides.contains_key(key) {
+                param.writable = false;
+            }
+        }
+        self.emit_parameters();
+        Ok(())
+    }
+
+    pub fn get_avatar_config(&self) -> Option<AvatarConfig> {
+        self.avatar_config.lock().unwrap().clone()
+    }
+
+    // Type declared for a parameter by the imported avatar config, if any
+    pub fn config_parameter_type(&self, name: &str) -> Option<ParameterType> {
+        self.avatar_config
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|config| config.parameter_types().remove(name))
+    }
+
+    // Address to send a parameter to: its own `osc_address`, then a custom address from the
+    // imported avatar config, then the usual prefix + name
+    pub fn parameter_osc_address(&self, name: &str) -> String {
+        self.get_parameter(name)
+            .and_then(|param| param.osc_address)
+            .or_else(|| self.config_osc_address(name))
+            .unwrap_or_else(|| parameter_address(name))
+    }
+
+    // Whether the imported avatar config declares a parameter without an input endpoint
+    fn config_read_only(&self, name: &str) -> bool {
+        self.avatar_config
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map_or(false, |config| config.read_only_parameters().contains(name))
+    }
+
+    fn config_osc_address(&self, name: &str) -> Option<String> {
+        self.avatar_config
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|config| config.custom_addresses().remove(name))
+    }
+
+    // Overwrite guessed types of discovered parameters with those declared by the imported
+    // avatar config, returning how many changed
+    pub fn apply_types_from_config(&self, avatar_id: &str) -> Result<usize, String> {
+        let config_types = match self.get_avatar_config() {
+            Some(config) if config.id == avatar_id => config.parameter_types(),
+            Some(config) => return Err(format!("Imported avatar config is for {}, not {}", config.id, avatar_id)),
+            None => return Err("No avatar config imported".to_string()),
+        };
+        
+        let mut corrected = 0;
+        let mut params = self.parameters.lock().unwrap();
+        for param in params.values_mut() {
+            if let Some(config_type) = config_types.get(&param.name) {
+                if *config_type != param.parameter_type {
+                    param.parameter_type = config_type.clone();
+                    corrected += 1;
+                }
+            }
+        }
+        drop(params); // Release the lock before emitting
+        
+        if corrected > 0 {
+            self.emit_parameters();
+        }
+        Ok(corrected)
+    }
+
+    pub fn load_queue_modes(&self) -> Result<(), String> {
+        if let Some(loaded_modes) = self.load_data::<HashMap<String, QueueMode>>("queue_modes.json")? {
+            *self.queue_modes.lock().unwrap() = self.keyed(loaded_modes);
+        }
+        Ok(())
+    }
+
+    pub fn set_queue_mode(&self, name: &str, mode: QueueMode) -> Result<(), String> {
+        let key = self.parameter_key(name);
+        let mut modes = self.queue_modes.lock().unwrap();
+        if mode == QueueMode::default() {
+            modes.remove(&key);
+        } else {
+            modes.insert(key, mode);
+        }
+        drop(modes); // Release the lock before saving
+        
+        self.save_queue_modes()
+    }
+
+    // Queue an outbound send, paced by the send worker
+    pub fn queue_parameter(&self, name: &str, value: f32, param_type: ParameterType) {
+        let case_insensitive = self.config.lock().unwrap().case_insensitive_names;
+        let key = name_key(name, case_insensitive);
+        let mode = self.queue_modes.lock().unwrap().get(&key).cloned().unwrap_or_default();
+        
+        let (queue, available) = &*self.send_queue;
+        let mut queue = queue.lock().unwrap();
+        
+        // Coalescing replaces a pending value in place so the parameter keeps its turn
+        let pending = match mode {
+            QueueMode::Coalesce => queue.iter().position(|q| name_key(&q.name, case_insensitive) == key),
+            QueueMode::Ordered => None,
+        };
+        
+        if let Some(idx) = pending {
+            queue[idx].value = value;
+            queue[idx].param_type = param_type;
+        } else {
+            queue.push_back(QueuedSend {
+                name: name.to_string(),
+                value,
+                param_type,
+            });
+        }
+        
+        available.notify_one();
+    }
+
+    // Multiply the send rate for `duration`, capped at the maximum rate. It reverts on its own
+    // once the duration passes, or earlier via end_burst with the returned id
+    pub fn start_burst(&self, multiplier: f32, duration: Duration) -> Result<u64, String> {
+        if !multiplier.is_finite() || multiplier < 1.0 {
+            return Err("Burst multiplier must be at least 1".to_string());
+        }
+        
+        let id = self.next_burst_id.fetch_add(1, Ordering::Relaxed);
+        *self.send_burst.lock().unwrap() = Some(SendBurst {
+            id,
+            multiplier,
+            until: Instant::now() + duration,
+        });
+        Ok(id)
+    }
+
+    // End a burst early; a newer burst that replaced it is left alone
+    pub fn end_burst(&self, id: u64) {
+        let mut burst = self.send_burst.lock().unwrap();
+        if burst.as_ref().map_or(false, |b| b.id == id) {
+            *burst = None;
+        }
+    }
+
+    // start_burst for the lifetime of the returned guard
+    pub fn with_burst(&self, multiplier: f32, duration: Duration) -> Result<SendBurstGuard<'_>, String> {
+        let id = self.start_burst(multiplier, duration)?;
+        Ok(SendBurstGuard { state: self, id })
+    }
+
+    // Sends waiting in the queue
+    pub fn pending_send_count(&self) -> usize {
+        self.send_queue.0.lock().unwrap().len()
+    }
+
+    fn effective_send_rate_hz(&self) -> f64 {
+        let base = self.config.lock().unwrap().send_rate_hz.max(MIN_SEND_RATE_HZ) as f64;
+        
+        let mut burst = self.send_burst.lock().unwrap();
+        match burst.as_ref() {
+            Some(b) if b.until > Instant::now() => (base * b.multiplier as f64).min(MAX_SEND_RATE_HZ as f64),
+            Some(_) => {
+                *burst = None; // Expired
+                base
+            }
+            None => base,
+        }
+    }
+
+    // Drain the send queue at the send rate limit
+    fn run_send_worker(&self) {
+        loop {
+            let next = {
+                let (queue, available) = &*self.send_queue;
+                let mut queue = queue.lock().unwrap();
+                while queue.is_empty() {
+                    queue = available.wait(queue).unwrap();
+                }
+                queue.pop_front()
+            };
+            
+            if let Some(item) = next {
+                match send_parameter(&item.name, item.value, &item.param_type, self) {
+                    // A newer value went out, so an older pending retry would only regress it
+                    Ok(()) => self.retry_queue.lock().unwrap().retain(|r| r.send.name != item.name),
+                    Err(e) => {
+                        log::error!("Failed to send queued parameter {}: {}", item.name, e);
+                        if e.is::<std::io::Error>() && self.config.lock().unwrap().retry_failed_sends {
+                            self.schedule_retry(item, 1);
+                        }
+                    }
+                }
+            }
+            
+            // Read the rate each time so config changes and bursts apply without restarting the worker
+            thread::sleep(Duration::from_secs_f64(1.0 / self.effective_send_rate_hz()));
+        }
+    }
+
+    // Queue a failed send for another attempt. The queue is bounded and keeps one entry per
+    // parameter, so a permanently failing network can't grow it without limit
+    fn schedule_retry(&self, send: QueuedSend, attempts: u32) {
+        let mut retries = self.retry_queue.lock().unwrap();
+        retries.retain(|r| r.send.name != send.name);
+        if retries.len() >= RETRY_QUEUE_LIMIT {
+            if let Some(dropped) = retries.pop_front() {
+                log::warn!("Retry queue full, dropping pending send for {}", dropped.send.name);
+            }
+        }
+        
+        let backoff = Duration::from_millis(RETRY_BACKOFF_MS * 2u64.pow(attempts - 1));
+        retries.push_back(RetrySend {
+            send,
+            attempts,
+            due: Instant::now() + backoff,
+        });
+    }
+
+    // Re-send failed parameters once their backoff has elapsed
+    fn run_retry_worker(&self) {
+        loop {
+            thread::sleep(Duration::from_millis(RETRY_POLL_MS));
+            
+            let due: VecDeque<RetrySend> = {
+                let mut retries = self.retry_queue.lock().unwrap();
+                let now = Instant::now();
+                let (due, waiting) = retries.drain(..).partition(|r| r.due <= now);
+                *retries = waiting;
+                due
+            };
+            
+            for retry in due {
+                let send = retry.send;
+                let error = match send_parameter(&send.name, send.value, &send.param_type, self) {
+                    Ok(()) => continue,
+                    Err(e) => e,
+                };
+                
+                if retry.attempts < RETRY_MAX_ATTEMPTS {
+                    self.schedule_retry(send, retry.attempts + 1);
+                    continue;
+                }
+                
+                log::error!("Giving up on {} after {} retries: {}", send.name, retry.attempts, error);
+                self.emit_event("send-failed", SendFailure {
+                    parameter_name: send.name,
+                    value: send.value,
+                    attempts: retry.attempts,
+                    error: error.to_string(),
+                });
+            }
+        }
+    }
+
+    // Stop the listener and send anything still queued before exit; persisted state is
+    // written through on every change, so there is nothing dirty to save here
+    pub fn shutdown(&self) {
+        if let Err(e) = self.stop_listener() {
+            log::error!("Failed to stop OSC listener: {}", e);
+        }
+        
+        let pending: Vec<QueuedSend> = self.send_queue.0.lock().unwrap().drain(..).collect();
+        for item in pending {
+            if let Err(e) = send_parameter(&item.name, item.value, &item.param_type, self) {
+                log::error!("Failed to flush queued parameter {}: {}", item.name, e);
+            }
+        }
+    }
+
+    // Notify frontend with the full parameter list if app handle is available. Subscribed
+    // windows get only their parameters instead, once one of them has changed since their last emit
+    pub(crate) fn emit_parameters(&self) {
+        let Some(app_handle) = self.app_handle.lock().unwrap().clone() else {
+            return;
+        };
+        if self.update_subscriptions.lock().unwrap().is_empty() {
+            self.emit_event("parameter-updated", self.get_parameters());
+            return;
+        }
+        
+        let labels: Vec<String> = app_handle.webview_windows().into_keys().collect();
+        for (label, event, parameters) in self.parameter_emits(&labels) {
+            self.emit_event_to(&label, event, parameters);
+        }
+    }
+
+    // What emit_parameters sends to each open window, as (label, event, parameters).
+    // Subscriptions of windows that have closed are dropped
+    fn parameter_emits(&self, labels: &[String]) -> Vec<(String, &'static str, Vec<Parameter>)> {
+        let parameters = self.get_parameters();
+        let mut subscriptions = self.update_subscriptions.lock().unwrap();
+        subscriptions.retain(|label, _| labels.contains(label));
+        
+        labels
+            .iter()
+            .filter_map(|label| match subscriptions.get_mut(label) {
+                None => Some((label.clone(), "parameter-updated", parameters.clone())),
+                Some(subscription) if subscription.dirty => {
+                    subscription.dirty = false;
+                    let subscribed = parameters
+                        .iter()
+                        .filter(|p| subscription.names.contains(&p.name))
+                        .cloned()
+                        .collect();
+                    Some((label.clone(), "subscribed-parameters-updated", subscribed))
+                }
+                Some(_) => None,
+            })
+            .collect()
+    }
+
+    // Limit a window's updates to `names`; an empty list goes back to the full list
+    pub fn set_update_subscription(&self, label: &str, names: Vec<String>) {
+        let mut subscriptions = self.update_subscriptions.lock().unwrap();
+        if names.is_empty() {
+            subscriptions.remove(label);
+        } else {
+            // Dirty from the start, so the subscriber gets the current values right away
+            let subscription = UpdateSubscription {
+                names: names.into_iter().collect(),
+                dirty: true,
+            };
+            subscriptions.insert(label.to_string(), subscription);
+        }
+        drop(subscriptions); // Release the lock before emitting
+        
+        self.emit_parameters();
+    }
+
+    // Emit an event to the frontend without blocking. Failures are only counted (and the first
+    // one logged) so "UI not updating" reports can be traced to the emitting side
+    pub(crate) fn emit_event<S: Serialize + Clone>(&self, event: &str, payload: S) {
+        self.emit_with(event, |app_handle| app_handle.emit(event, payload));
+    }
+
+    fn emit_event_to<S: Serialize + Clone>(&self, label: &str, event: &str, payload: S) {
+        self.emit_with(event, |app_handle| app_handle.emit_to(label, event, payload));
+    }
+
+    fn emit_with(&self, event: &str, emit: impl FnOnce(&AppHandle) -> tauri::Result<()>) {
+        if let Some(app_handle) = self.app_handle.lock().unwrap().as_ref() {
+            if let Err(e) = emit(app_handle) {
+                if self.emit_failures.fetch_add(1, Ordering::Relaxed) == 0 {
+                    log::warn!("Failed to emit {} event: {}", event, e);
+                }
+            }
+        }
+    }
+
+    // Inbound updates per second for each parameter seen, to spot the noisiest ones
+    pub fn get_parameter_rates(&self) -> HashMap<String, u32> {
+        let now = Instant::now();
+        let params = self.parameters.lock().unwrap();
+        self.update_rates
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(key, rate)| {
+                // Report under the parameter's own spelling where it is known
+                let name = params.get(key).map_or_else(|| key.clone(), |p| p.name.clone());
+                (name, rate.rate(now))
+            })
+            .collect()
+    }
+
+    fn record_observed_address(&self, address: &str) {
+        let now_ms = unix_millis();
+        let mut observed = self.observed_addresses.lock().unwrap();
+        
+        if let Some(entry) = observed.get_mut(address) {
+            entry.count += 1;
+            entry.last_seen_ms = now_ms;
+            return;
+        }
+        
+        if observed.len() >= MAX_OBSERVED_ADDRESSES {
+            let oldest = observed.values().min_by_key(|a| a.last_seen_ms).map(|a| a.address.clone());
+            if let Some(oldest) = oldest {
+                observed.remove(&oldest);
+            }
+        }
+        observed.insert(
+            address.to_string(),
+            ObservedAddress {
+                address: address.to_string(),
+                count: 1,
+                last_seen_ms: now_ms,
+            },
+        );
+    }
+
+    // Every distinct address received this session, sorted by address
+    pub fn get_observed_addresses(&self) -> Vec<ObservedAddress> {
+        let mut addresses: Vec<ObservedAddress> = self.observed_addresses.lock().unwrap().values().cloned().collect();
+        addresses.sort_by(|a, b| a.address.cmp(&b.address));
+        addresses
+    }
+
+    pub fn get_send_bandwidth(&self) -> BandwidthStats {
+        self.send_bandwidth.stats()
+    }
+
+    // Number of frontend events that failed to emit this session
+    pub fn get_emit_failure_count(&self) -> u64 {
+        self.emit_failures.load(Ordering::Relaxed)
+    }
+
+    // Get all parameters
+    // Sorted by name so the UI list and event payloads keep a stable order
+    pub fn get_parameters(&self) -> Vec<Parameter> {
+        let mut params: Vec<Parameter> = self.parameters.lock().unwrap().values().cloned().collect();
+        params.sort_by(|a, b| a.name.cmp(&b.name));
+        for (index, param) in params.iter_mut().enumerate() {
+            param.index = Some(index);
+        }
+        params
+    }
+
+    // Parameter at `index` in the sorted list. Indices are only stable while the parameter set
+    // doesn't change, so controllers should re-read the list after a `parameter-discovered`
+    pub fn get_parameter_by_index(&self, index: usize) -> Option<Parameter> {
+        self.get_parameters().into_iter().nth(index)
+    }
+
+    // Queue a value for the parameter at `index`, returning the resolved name
+    pub fn set_parameter_by_index(&self, index: usize, value: f32) -> Result<String, String> {
+        let param = self
+            .get_parameter_by_index(index)
+            .ok_or_else(|| format!("No parameter at index {}", index))?;
+        
+        self.ensure_writable(&param.name).map_err(|e| e.to_string())?;
+        ensure_scalar(&param.name, &param.parameter_type).map_err(|e| e.to_string())?;
+        param.parameter_type.validate_value(value)?;
+        self.queue_parameter(&param.name, value, param.parameter_type.clone());
+        self.set_or_insert_parameter(&param.name, value, param.parameter_type);
+        
+        Ok(param.name)
+    }
+
+    pub fn get_parameter(&self, name: &str) -> Option<Parameter> {
+        let key = self.parameter_key(name);
+        self.parameters.lock().unwrap().get(&key).cloned()
+    }
+
+    // Map key for a parameter name, honouring the case-insensitive keying mode
+    fn parameter_key(&self, name: &str) -> String {
+        name_key(name, self.config.lock().unwrap().case_insensitive_names)
+    }
+
+    // Re-key a per-parameter settings map read from disk or a snapshot
+    fn keyed<V>(&self, map: HashMap<String, V>) -> HashMap<String, V> {
+        let case_insensitive = self.config.lock().unwrap().case_insensitive_names;
+        map.into_iter().map(|(name, value)| (name_key(&name, case_insensitive), value)).collect()
+    }
+
+    // Re-key the per-parameter settings after the keying mode changes. Going back to exact names,
+    // keys take the spelling of a known parameter where there is one
+    fn rekey_settings(&self) {
+        let case_insensitive = self.config.lock().unwrap().case_insensitive_names;
+        let canonical: HashMap<String, String> = self
+            .parameters
+            .lock()
+            .unwrap()
+            .values()
+            .map(|p| (p.name.to_lowercase(), p.name.clone()))
+            .collect();
+        let rekey = |name: String| {
+            if case_insensitive {
+                name.to_lowercase()
+            } else {
+                canonical.get(&name).cloned().unwrap_or(name)
+            }
+        };
+        
+        rekey_map(&self.labels, &rekey);
+        rekey_map(&self.curves, &rekey);
+        rekey_map(&self.send_curves, &rekey);
+        rekey_map(&self.writable_overrides, &rekey);
+        rekey_map(&self.queue_modes, &rekey);
+        rekey_map(&self.update_rates, &rekey);
+        
+        let saves = [
+            self.save_labels(),
+            self.save_curves(),
+            self.save_send_curves(),
+            self.save_writable_overrides(),
+            self.save_queue_modes(),
+        ];
+        for e in saves.into_iter().filter_map(Result::err) {
+            log::error!("Failed to save re-keyed parameter settings: {}", e);
+        }
+    }
+
+    // Re-key the parameter map after the keying mode changes; colliding entries merge into one
+    fn rekey_parameters(&self) {
+        let mut params = self.parameters.lock().unwrap();
+        let old = std::mem::take(&mut *params);
+        for (_, param) in old {
+            params.insert(self.parameter_key(&param.name), param);
+        }
+    }
+
+    // VRChat built-in parameters currently known, sorted by name
+    pub fn get_meta_parameters(&self) -> Vec<Parameter> {
+        self.get_parameters().into_iter().filter(|p| p.is_meta).collect()
+    }
+
+    // Group by menu path where the avatar config provides one, falling back to the part of the
+    // name before the first `/`. Groups and their parameters are sorted by name
+    pub fn get_parameters_grouped(&self) -> Vec<ParameterGroup> {
+        let mut groups: BTreeMap<(String, bool), Vec<Parameter>> = BTreeMap::new();
+        for param in self.get_parameters() {
+            let key = match (&param.menu_path, param.name.split_once('/')) {
+                (Some(menu_path), _) => (menu_path.clone(), true),
+                (None, Some((prefix, _))) => (prefix.to_string(), false),
+                (None, None) => (String::new(), false),
+            };
+            groups.entry(key).or_default().push(param);
+        }
+        
+        groups
+            .into_iter()
+            .map(|((name, from_menu), parameters)| ParameterGroup { name, from_menu, parameters })
+            .collect()
+    }
+
+    // Best fuzzy matches for `query` among discovered parameters and the imported avatar config,
+    // highest score first; ties prefer shorter names
+    pub fn suggest_parameters(&self, query: &str, limit: usize) -> Vec<ParameterSuggestion> {
+        let mut names: HashSet<String> = self.parameters.lock().unwrap().values().map(|p| p.name.clone()).collect();
+        if let Some(config) = self.avatar_config.lock().unwrap().as_ref() {
+            names.extend(config.parameters.iter().map(|p| p.name.clone()));
+        }
+        
+        let mut suggestions: Vec<ParameterSuggestion> = names
+            .into_iter()
+            .filter_map(|name| {
+                let (score, matched) = fuzzy_score(query, &name)?;
+                Some(ParameterSuggestion { name, score, matched })
+            })
+            .collect();
+        
+        suggestions.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then(a.name.len().cmp(&b.name.len()))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        suggestions.truncate(limit);
+        suggestions
+    }
+
+    // Whether VRChat reports this avatar as the local player's (false until IsLocal arrives)
+    pub fn is_local(&self) -> bool {
+        self.get_parameter("IsLocal").map_or(false, |p| p.value > 0.5)
+    }
+
+    // Look up a parameter by exact name, optionally falling back to a trimmed, case-insensitive match
+    pub fn find_parameter(&self, name: &str, normalize: bool) -> Option<Parameter> {
+        let key = self.parameter_key(name);
+        let params = self.parameters.lock().unwrap();
+        if let Some(param) = params.get(&key) {
+            return Some(param.clone());
+        }
+        if !normalize {
+            return None;
+        }
+        
+        let wanted = name.trim().to_lowercase();
+        params.values().find(|p| p.name.trim().to_lowercase() == wanted).cloned()
+    }
+
+    // Type a send would use: explicit override, then the discovered type, then the imported
+    // avatar config, then the fallback
+    pub fn resolve_send_type(&self, name: &str, override_type: Option<ParameterType>, fallback: ParameterType) -> ParameterType {
+        override_type
+            .or_else(|| self.get_parameter(name).map(|p| p.parameter_type))
+            .or_else(|| self.config_parameter_type(name))
+            .unwrap_or(fallback)
+    }
+
+    // Record a value set from the UI: `value` becomes what its send curve turned it into
+    pub fn set_parameter(&self, name: &str, value: f32) -> Result<()> {
+        let key = self.parameter_key(name);
+        let mut params = self.parameters.lock().unwrap();
+        
+        if let Some(param) = params.get_mut(&key) {
+            param.value = self.outbound_value(&param.name, value, &param.parameter_type);
+            param.ui_value = Some(value);
+            Ok(())
+        } else {
+            Err(anyhow!("Parameter not found: {}", name))
+        }
+    }
+
+    // Set a parameter's value, inserting it with the given type if it hasn't been discovered yet
+    // (e.g. a preset applied before the avatar has sent anything)
+    pub fn set_or_insert_parameter(&self, name: &str, value: f32, param_type: ParameterType) {
+        if self.set_parameter(name, value).is_err() {
+            let mut param = Parameter::new(name, param_type, 0.0);
+            param.value = self.outbound_value(name, value, &param.parameter_type);
+            param.ui_value = Some(value);
+            self.store_parameter(param);
+        }
+    }
+
+    // Move a stored parameter to a new name, e.g. after an avatar update renamed it. This is local
+    // bookkeeping only; nothing is renamed in VRChat. Renaming onto an existing parameter is an
+    // error rather than a merge, so no value is silently lost
+    pub fn rename_parameter(&self, old: &str, new: &str) -> Result<Parameter, String> {
+        let new = new.trim();
+        if new.is_empty() {
+            return Err("New parameter name must not be empty".to_string());
+        }
+        
+        let (old_key, new_key) = (self.parameter_key(old), self.parameter_key(new));
+        let mut params = self.parameters.lock().unwrap();
+        if old_key != new_key && params.contains_key(&new_key) {
+            return Err(format!("Parameter already exists: {}", new));
+        }
+        let mut param = params
+            .remove(&old_key)
+            .ok_or_else(|| format!("Parameter not found: {}", old))?;
+        param.name = new.to_string();
+        params.insert(new_key.clone(), param.clone());
+        drop(params); // Release the lock before saving
+        
+        // Carry the per-name settings over so they survive the next inbound update
+        let mut labels = self.labels.lock().unwrap();
+        if let Some(label) = labels.remove(&old_key) {
+            labels.insert(new_key.clone(), label);
+        }
+        drop(labels);
+        let mut curves = self.curves.lock().unwrap();
+        if let Some(curve) = curves.remove(&old_key) {
+            curves.insert(new_key.clone(), curve);
+        }
+        drop(curves);
+        let mut send_curves = self.send_curves.lock().unwrap();
+        if let Some(curve) = send_curves.remove(&old_key) {
+            send_curves.insert(new_key.clone(), curve);
+        }
+        drop(send_curves);
+        let mut overrides = self.writable_overrides.lock().unwrap();
+        if let Some(writable) = overrides.remove(&old_key) {
+            overrides.insert(new_key.clone(), writable);
+        }
+        drop(overrides);
+        let mut modes = self.queue_modes.lock().unwrap();
+        if let Some(mode) = modes.remove(&old_key) {
+            modes.insert(new_key.clone(), mode);
+        }
+        drop(modes);
+        let mut rates = self.update_rates.lock().unwrap();
+        if let Some(rate) = rates.remove(&old_key) {
+            rates.insert(new_key.clone(), rate);
+        }
+        drop(rates);
+        let case_insensitive = self.config.lock().unwrap().case_insensitive_names;
+        let mut watches = self.watches.lock().unwrap();
+        for watch in watches.iter_mut().filter(|w| name_key(&w.parameter_name, case_insensitive) == old_key) {
+            watch.parameter_name = param.name.clone();
+        }
+        drop(watches); // Release the locks before saving
+        self.save_labels()?;
+        self.save_curves()?;
+        self.save_send_curves()?;
+        self.save_writable_overrides()?;
+        self.save_queue_modes()?;
+        self.save_watches()?;
+        
+        self.emit_parameters();
+        Ok(param)
+    }
+
+    // Drop every parameter whose name matches a `*` glob or substring, e.g. stale "Old/*" entries,
+    // along with its per-name settings so they don't come back with a later parameter of that name
+    pub fn remove_parameters_matching(&self, pattern: &str) -> Result<usize, String> {
+        if pattern.trim().is_empty() {
+            return Err("Pattern must not be empty".to_string());
+        }
+        
+        let mut params = self.parameters.lock().unwrap();
+        let removed: HashSet<String> = params
+            .iter()
+            .filter(|(_, param)| pattern_matches(pattern, &param.name))
+            .map(|(key, _)| key.clone())
+            .collect();
+        params.retain(|key, _| !removed.contains(key));
+        drop(params); // Release the lock before saving
+        
+        if removed.is_empty() {
+            return Ok(0);
+        }
+        
+        remove_keys(&self.labels, &removed);
+        remove_keys(&self.curves, &removed);
+        remove_keys(&self.send_curves, &removed);
+        remove_keys(&self.writable_overrides, &removed);
+        remove_keys(&self.queue_modes, &removed);
+        remove_keys(&self.update_rates, &removed);
+        let case_insensitive = self.config.lock().unwrap().case_insensitive_names;
+        self.watches
+            .lock()
+            .unwrap()
+            .retain(|w| !removed.contains(&name_key(&w.parameter_name, case_insensitive)));
+        self.save_labels()?;
+        self.save_curves()?;
+        self.save_send_curves()?;
+        self.save_writable_overrides()?;
+        self.save_queue_modes()?;
+        self.save_watches()?;
+        
+        self.emit_parameters();
+        Ok(removed.len())
+    }
+
+    // Whether an inbound float is too close to the stored value to be worth an update
+    fn within_deadband(&self, param: &Parameter, deadband: f32) -> bool {
+        if !matches!(param.parameter_type, ParameterType::Float) {
+            return false;
+        }
+        match self.get_parameter(&param.name) {
+            Some(stored) if matches!(stored.parameter_type, ParameterType::Float) => {
+                (param.value - stored.value).abs() <= deadband
+            }
+            _ => false,
+        }
+    }
+
+    pub fn export_state(&self) -> StateSnapshot {
+        StateSnapshot {
+            parameters: self.get_parameters(),
+            config: self.get_config(),
+            labels: self.labels.lock().unwrap().clone(),
+        }
+    }
+
+    // Replace parameters, config and labels with a snapshot, then refresh the frontend
+    pub fn import_state(&self, snapshot: StateSnapshot) -> Result<(), String> {
+        self.update_config(snapshot.config)
+            .map_err(|e| format!("Failed to apply config: {}", e))?;
+        
+        *self.labels.lock().unwrap() = self.keyed(snapshot.labels);
+        self.save_labels()?;
+        
+        self.parameters.lock().unwrap().clear();
+        for param in snapshot.parameters {
+            self.store_parameter(param);
+        }
+        self.emit_parameters();
+        
+        Ok(())
+    }
+
+    // Resolve with the next value VRChat sends for `name`, or None after the timeout
+    pub async fn await_parameter_change(&self, name: &str, timeout: Duration) -> Option<Parameter> {
+        let mut waiter = self.register_change_waiter(name);
+        let result = tokio::time::timeout(timeout, &mut waiter.rx).await.ok().and_then(Result::ok);
+        result
+    }
+
+    fn register_change_waiter(&self, name: &str) -> ChangeWaiter<'_> {
+        let (tx, rx) = oneshot::channel();
+        self.change_waiters.lock().unwrap().push((name.to_string(), tx));
+        ChangeWaiter { state: self, rx }
+    }
+
+    // Queue a value, then wait until VRChat echoes one within `tolerance` of what was sent. The
+    // send goes through the queue like any other, so the rate limit applies; the waiter is
+    // registered before queueing so a fast echo can't be missed
+    pub async fn set_and_confirm(
+        &self,
+        name: &str,
+        value: f32,
+        param_type: ParameterType,
+        timeout: Duration,
+        tolerance: f32,
+    ) -> Result<ConfirmResult> {
+        let canonical = self.get_parameter(name).map_or_else(|| name.to_string(), |p| p.name);
+        self.ensure_writable(&canonical)?;
+        ensure_scalar(&canonical, &param_type)?;
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut waiter = self.register_change_waiter(&canonical);
+        
+        self.queue_parameter(&canonical, value, param_type.clone());
+        self.set_or_insert_parameter(&canonical, value, param_type.clone());
+        self.emit_parameters();
+        
+        // What VRChat should report back once the send has been applied
+        let sent = self.outbound_value(&canonical, value, &param_type);
+        let expected = match param_type {
+            ParameterType::Int => (sent as i32) as f32,
+            ParameterType::Bool => if sent > self.get_config().bool_threshold { 1.0 } else { 0.0 },
+            ParameterType::Float | ParameterType::Color => sent,
+        };
+        
+        let mut result = ConfirmResult { confirmed: false, reported: None };
+        while let Ok(Ok(reported)) = tokio::time::timeout_at(deadline, &mut waiter.rx).await {
+            result.confirmed = (reported.value - expected).abs() <= tolerance;
+            result.reported = Some(reported);
+            if result.confirmed {
+                break;
+            }
+            // An older in-flight value arrived first; keep waiting for ours
+            waiter = self.register_change_waiter(&canonical);
+        }
+        
+        Ok(result)
+    }
+
+    // Ground truth for one parameter: wait for VRChat's next report of it, falling back to the
+    // cached value when nothing arrives before the timeout
+    pub async fn query_parameter(&self, name: &str, timeout: Duration) -> ParameterQueryResult {
+        let cached = self.get_parameter(name);
+        let canonical = cached.as_ref().map_or_else(|| name.to_string(), |p| p.name.clone());
+        
+        match self.await_parameter_change(&canonical, timeout).await {
+            Some(reported) => ParameterQueryResult { parameter: Some(reported), from_vrchat: true },
+            None => ParameterQueryResult { parameter: cached, from_vrchat: false },
+        }
+    }
+
+    fn notify_change_waiters(&self, param: &Parameter) {
+        let mut waiters = self.change_waiters.lock().unwrap();
+        if waiters.is_empty() {
+            return;
+        }
+        
+        let (ready, waiting): (Vec<_>, Vec<_>) = waiters.drain(..).partition(|(name, _)| *name == param.name);
+        *waiters = waiting;
+        drop(waiters); // Release the lock before waking waiters
+        
+        for (_, tx) in ready {
+            let _ = tx.send(param.clone());
+        }
+    }
+
+    // Add or update parameter
+    pub fn update_parameter(&self, param: Parameter) {
+        self.store_parameter(param);
+        self.emit_parameters();
+    }
+
+    // Insert a parameter into the map, applying its label and announcing new ones
+    pub(crate) fn store_parameter(&self, mut param: Parameter) -> Parameter {
+        let key = self.parameter_key(&param.name);
+        param.is_meta = self.config.lock().unwrap().meta_parameters.contains(&param.name);
+        
+        // In case-insensitive mode the first-seen spelling stays canonical, and an address set
+        // on the parameter survives later updates
+        let existing = self.parameters.lock().unwrap().get(&key).cloned();
+        match existing {
+            Some(existing) => {
+                param.name = existing.name;
+                param.osc_address = param.osc_address.take().or(existing.osc_address);
+                param.menu_path = existing.menu_path;
+                // VRChat echoing the value we sent keeps the UI value that produced it
+                if param.ui_value.is_none() && param.value == existing.value {
+                    param.ui_value = existing.ui_value;
+                }
+            }
+            // Avatar config details are looked up once, when the parameter is first seen;
+            // set_avatar_config refreshes them for known parameters
+            None => {
+                if param.osc_address.is_none() {
+                    param.osc_address = self.config_osc_address(&param.name);
+                }
+                param.menu_path = self.avatar_config
+                    .lock()
+                    .unwrap()
+                    .as_ref()
+                    .and_then(|config| config.menu_paths().remove(&param.name));
+                if self.config_read_only(&param.name) {
+                    param.writable = false;
+                }
+            }
+        }
+        
+        param.display_name = self.labels.lock().unwrap().get(&key).cloned();
+        param.shaped_value = self.curves.lock().unwrap().get(&key).map(|curve| curve.apply(param.value));
+        
+        let mut params = self.parameters.lock().unwrap();
+        
+        // A manual override wins; otherwise once a source reports a parameter as read-only it
+        // stays that way, since plain OSC messages carry no access information
+        param.writable = match self.writable_overrides.lock().unwrap().get(&key) {
+            Some(writable) => *writable,
+            None => param.writable && params.get(&key).map_or(true, |p| p.writable),
+        };
+        
+        let discovered = params.insert(key, param.clone()).is_none();
+        drop(params); // Release the lock before emitting
+        
+        for subscription in self.update_subscriptions.lock().unwrap().values_mut() {
+            if subscription.names.contains(&param.name) {
+                subscription.dirty = true;
+            }
+        }
+        
+        if discovered {
+            self.emit_event("parameter-discovered", param.clone());
+        }
+        
+        self.evaluate_watches(&param);
+        
+        param
+    }
+    
+    // Run a send on the shared outbound socket, binding it on first use or when the bind address
+    // changed. A failed send drops the socket so the next one starts from a fresh bind
+    fn with_send_socket<T>(&self, bind_address: &str, send: impl FnOnce(&UdpSocket) -> std::io::Result<T>) -> Result<T> {
+        let mut slot = self.send_socket.lock().unwrap();
+        let socket = match slot.take() {
+            Some((address, socket)) if address == bind_address => socket,
+            _ => UdpSocket::bind(SocketAddr::from_str(bind_address)?)?,
+        };
+        
+        let result = send(&socket)?;
+        *slot = Some((bind_address.to_string(), socket));
+        Ok(result)
+    }
+
+    fn reset_send_socket(&self) {
+        *self.send_socket.lock().unwrap() = None;
+    }
+
+    // Restore the previous config after the new one failed to bind, restart the listener on it
+    // and report why. Returns the original bind error for the caller
+    fn rollback_config(&self, previous_config: OscConfig, error: anyhow::Error) -> anyhow::Error {
+        log::error!("Failed to start OSC listener with new config, rolling back: {}", error);
+        *self.config.lock().unwrap() = previous_config;
+        
+        if let Err(restart_error) = self.start_listener() {
+            log::error!("Failed to restart OSC listener with previous config: {}", restart_error);
+        }
+        
+        self.emit_event("osc-config-rollback", error.to_string());
+        error
+    }
+
+    // Update OSC configuration
+    pub fn update_config(&self, new_config: OscConfig) -> Result<()> {
+        new_config.validate()?;
+        
+        let mut config = self.config.lock().unwrap();
+        
+        // Check if the configuration has changed
+        let rekey = config.case_insensitive_names != new_config.case_insensitive_names;
+        let send_bind_changed = config.send_bind_address != new_config.send_bind_address;
+        let config_changed = config.target_address != new_config.target_address 
+            || config.target_port != new_config.target_port
+            || config.listen_address != new_config.listen_address
+            || config.listen_port != new_config.listen_port;
+            
+        // Update config, keeping the last known good one in case the listener can't rebind
+        let previous_config = std::mem::replace(&mut *config, new_config);
+        
+        drop(config);  // Release lock before calling other methods
+        
+        // Sends must originate from the new address, so close the socket bound to the old one
+        if send_bind_changed {
+            self.reset_send_socket();
+        }
+        
+        // If the configuration has changed and we're running, restart the listener
+        if config_changed {
+            // Stop and restart the listener
+            if *self.running.lock().unwrap() {
+                self.stop_listener()?;
+                if let Err(e) = self.start_listener() {
+                    return Err(self.rollback_config(previous_config, e));
+                }
+            }
+        }
+        
+        if rekey {
+            self.rekey_settings();
+            self.rekey_parameters();
+            self.emit_parameters();
+        }
+        
+        set_compact_json(self.get_config().compact_json);
+        if let Err(e) = self.save_config() {
+            log::error!("Failed to save OSC config: {}", e);
+        }
+        
+        let config = self.get_config();
+        if config.is_self_send() && !config.allow_loopback {
+            let warning = format!(
+                "OSC target {}:{} is this app's own listener; sent parameters will echo back",
+                config.target_address, config.target_port
+            );
+            log::warn!("{}", warning);
+            self.emit_event("osc-config-warning", warning);
+        }
+        
+        self.emit_event("osc-config-updated", self.get_config());
+        
+        Ok(())
+    }
+    
+    // Get current OSC configuration
+    pub fn get_config(&self) -> OscConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    // Network simulation settings, only honoured in debug builds
+    fn debug_network(&self) -> Option<DebugNetworkConfig> {
+        if !cfg!(debug_assertions) {
+            return None;
+        }
+        self.config.lock().unwrap().debug_network.clone()
+    }
+    
+    // Start OSC listener with current configuration
+    pub fn start_listener(&self) -> Result<()> {
+        let mut running = self.running.lock().unwrap();
+        if *running {
+            return Ok(());  // Already running
+        }
+        
+        let config = self.config.lock().unwrap().clone();
+        let listen_addr = format!("{}:{}", config.listen_address, config.listen_port);
+        
+        // Port 0 picks a free port, so it can't conflict
+        if config.listen_port != 0 {
+            self.check_listener_lock(config.listen_port)?;
+        }
+        let mut socket = bind_listener_socket(&listen_addr)?;
+        *self.bound_address.lock().unwrap() = socket.local_addr().ok();
+        if config.listen_port != 0 {
+            self.acquire_listener_lock(config.listen_port);
+        }
+        
+        log::info!("OSC listener started on {}", listen_addr);
+        
+        self.decode_errors.store(0, Ordering::Relaxed);
+        self.last_packet_ms.store(0, Ordering::Relaxed);
+        self.send_bandwidth.reset();
+        let state = self.clone();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        
+        *running = true;
+        
+        // Spawn a thread to listen for OSC messages
+        let handle = thread::spawn(move || {
+            let mut buf = [0u8; 1024];
+            let mut backoff = ListenerBackoff::new();
+            let mut last_emit: Option<Instant> = None;
+            let mut pending_emit = false;
+            
+            while !thread_stop.load(Ordering::Relaxed) {
+                // Emit batched updates once the debounce window has passed
+                if pending_emit {
+                    let debounce = Duration::from_millis(state.config.lock().unwrap().event_debounce_ms);
+                    if last_emit.map_or(true, |t| t.elapsed() >= debounce) {
+                        state.emit_parameters();
+                        last_emit = Some(Instant::now());
+                        pending_emit = false;
+                    }
+                }
+                
+                match socket.recv_from(&mut buf) {
+                    Ok((size, _addr)) => {
+                        backoff.reset();
+                        state.packets_received.fetch_add(1, Ordering::Relaxed);
+                        state.last_packet_ms.store(monotonic_ms() + 1, Ordering::Relaxed);
+                        
+                        // While paused the socket stays bound but packets are drained and discarded
+                        if state.paused.load(Ordering::Relaxed) {
+                            continue;
+                        }
+                        
+                        if let Some(debug_network) = state.debug_network() {
+                            if debug_network.should_drop() {
+                                log::debug!("Simulated drop of received OSC packet");
+                                continue;
+                            }
+                            thread::sleep(Duration::from_millis(debug_network.recv_delay_ms));
+                        }
+                        
+                        match rosc::decoder::decode_udp(&buf[..size]) {
+                            Ok((_, packet)) => {
+                                // Process the packet and notify frontend if parameters were updated
+                                if state.process_osc_packet(packet).is_some() {
+                                    pending_emit = true;
+                                }
+                            }
+                            Err(e) => state.record_decode_error(&buf[..size], e),
+                        }
+                    }
+                    Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        thread::sleep(Duration::from_millis(10));
+                    }
+                    Err(e) => {
+                        log::error!("Error receiving OSC: {}", e);
+                        let delay = match backoff.on_error(&e) {
+                            RecvErrorAction::Retry(delay) => {
+                                thread::sleep(delay);
+                                continue;
+                            }
+                            RecvErrorAction::Rebind(delay) => delay,
+                        };
+                        
+                        // The socket looks dead, release it and try to bind a fresh one
+                        log::warn!("OSC listener socket failed {} times, rebinding {}", backoff.consecutive_errors, listen_addr);
+                        drop(socket);
+                        thread::sleep(delay);
+                        if thread_stop.load(Ordering::Relaxed) {
+                            break;
+                        }
+                        
+                        let rebind = bind_listener_socket(&listen_addr);
+                        // Shared state is only touched under the running lock and while this
+                        // thread is still the current listener
+                        let mut running = state.running.lock().unwrap();
+                        if thread_stop.load(Ordering::Relaxed) {
+                            break;
+                        }
+                        match rebind {
+                            Ok(new_socket) => {
+                                *state.bound_address.lock().unwrap() = new_socket.local_addr().ok();
+                                socket = new_socket;
+                                backoff.reset();
+                            }
+                            Err(bind_error) => {
+                                log::error!("Failed to rebind OSC listener: {}", bind_error);
+                                *running = false;
+                                drop(running); // Release the lock before emitting
+                                *state.bound_address.lock().unwrap() = None;
+                                state.release_listener_lock();
+                                state.emit_event("osc-listener-failed", bind_error.to_string());
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        });
+        
+        let mut thread_ref = self.listener_thread.lock().unwrap();
+        *thread_ref = Some(ListenerThread { handle, stop });
+        
+        Ok(())
+    }
+    
+    // Stop OSC listener
+    pub fn stop_listener(&self) -> Result<()> {
+        let mut running = self.running.lock().unwrap();
+        if !*running {
+            return Ok(());  // Not running
+        }
+        
+        // Signal thread to stop
+        *running = false;
+        let listener = self.listener_thread.lock().unwrap().take();
+        if let Some(listener) = &listener {
+            listener.stop.store(true, Ordering::Relaxed);
+        }
+        drop(running); // Release the lock so a failing rebind in the thread can finish
+        *self.bound_address.lock().unwrap() = None;
+        self.release_listener_lock();
+        
+        // Join thread
+        if let Some(ListenerThread { handle, .. }) = listener {
+            let timeout = Duration::from_millis(self.config.lock().unwrap().stop_timeout_ms);
+            
+            // Join on a helper thread so a wedged listener can't hang the caller
+            let (tx, rx) = mpsc::channel();
+            thread::spawn(move || {
+                let _ = tx.send(handle.join().is_ok());
+            });
+            
+            match rx.recv_timeout(timeout) {
+                Ok(true) => {}
+                Ok(false) => log::warn!("OSC listener thread did not exit cleanly"),
+                Err(_) => {
+                    // The thread owns the socket, so it is closed once the thread wakes up and
+                    // sees its own stop flag; a restart in the meantime gets a fresh flag, so the
+                    // detached thread never resumes. The helper keeps waiting on it until then
+                    log::warn!("OSC listener thread did not stop within {:?}, detaching it", timeout);
+                }
+            }
+        }
+        
+        Ok(())
+    }
+
+    // The listener thread has finished although nobody stopped it. A failed rebind clears
+    // `running` itself, so that case is not treated as a crash
+    fn listener_died(&self) -> bool {
+        let running = self.running.lock().unwrap();
+        *running && self.listener_thread.lock().unwrap().as_ref().map_or(false, |t| t.handle.is_finished())
+    }
+
+    // Restart the listener, with backoff, whenever its thread dies unexpectedly
+    fn run_listener_watchdog(&self) {
+        let mut backoff = Duration::from_millis(LISTENER_BACKOFF_MIN_MS);
+        let mut last_restart: Option<Instant> = None;
+        
+        loop {
+            thread::sleep(WATCHDOG_POLL);
+            if !self.listener_died() {
+                continue;
+            }
+            
+            if last_restart.map_or(false, |t| t.elapsed() >= WATCHDOG_STABLE_AFTER) {
+                backoff = Duration::from_millis(LISTENER_BACKOFF_MIN_MS);
+            }
+            log::error!("OSC listener thread exited unexpectedly, restarting in {:?}", backoff);
+            thread::sleep(backoff);
+            backoff = (backoff * 2).min(Duration::from_millis(LISTENER_BACKOFF_MAX_MS));
+            
+            // Check again in case the listener was stopped or restarted while we waited
+            let mut running = self.running.lock().unwrap();
+            if !*running || !self.listener_thread.lock().unwrap().as_ref().map_or(false, |t| t.handle.is_finished()) {
+                continue;
+            }
+            if let Some(listener) = self.listener_thread.lock().unwrap().take() {
+                if listener.handle.join().is_err() {
+                    log::error!("OSC listener thread panicked");
+                }
+            }
+            *running = false;
+            drop(running); // Release the lock before starting a new listener
+            
+            last_restart = Some(Instant::now());
+            match self.start_listener() {
+                Ok(()) => {
+                    log::info!("OSC listener restarted by watchdog");
+                    self.emit_event("osc-listener-restarted", ());
+                }
+                Err(e) => {
+                    // Leave `running` false so the failure is visible; the user can retry
+                    log::error!("Failed to restart OSC listener: {}", e);
+                    self.emit_event("osc-listener-failed", e.to_string());
+                }
+            }
+        }
+    }
+
+    // Count a packet that failed to decode, logging the first few and reporting in batches
+    fn record_decode_error(&self, data: &[u8], error: rosc::OscError) {
+        let count = self.decode_errors.fetch_add(1, Ordering::Relaxed) + 1;
+        
+        if count <= DECODE_ERROR_LOG_LIMIT {
+            let snippet: Vec<String> = data.iter().take(32).map(|b| format!("{:02x}", b)).collect();
+            log::warn!(
+                "Failed to decode OSC packet ({} bytes): {:?} [{}]",
+                data.len(),
+                error,
+                snippet.join(" ")
+            );
+        }
+        
+        let threshold = self.config.lock().unwrap().decode_error_threshold;
+        if threshold > 0 && count % threshold == 0 {
+            log::warn!("{} OSC packets failed to decode, check the port and protocol", count);
+            self.emit_event("osc-decode-errors", count);
+        }
+    }
+
+    // Total packets received by the listener this session
+    pub fn get_packets_received(&self) -> u64 {
+        self.packets_received.load(Ordering::Relaxed)
+    }
+
+    // Seconds since the listener last received anything, None if nothing arrived since it started
+    pub fn seconds_since_last_packet(&self) -> Option<f64> {
+        match self.last_packet_ms.load(Ordering::Relaxed) {
+            0 => None,
+            stamp => Some(monotonic_ms().saturating_sub(stamp - 1) as f64 / 1000.0),
+        }
+    }
+
+    // Messages dropped by the accept/ignore filters this session
+    pub fn get_filtered_message_count(&self) -> u64 {
+        self.filtered_messages.load(Ordering::Relaxed)
+    }
+
+    // Avatar parameter messages whose argument type could not be stored this session
+    pub fn get_unsupported_message_count(&self) -> u64 {
+        self.unsupported_messages.load(Ordering::Relaxed)
+    }
+
+    // Address the listener socket actually bound to, resolving wildcard hosts and port 0
+    pub fn get_bound_address(&self) -> Option<SocketAddr> {
+        *self.bound_address.lock().unwrap()
+    }
+
+    pub fn is_listener_running(&self) -> bool {
+        *self.running.lock().unwrap()
+    }
+
+    // Stop processing inbound packets without releasing the listener socket
+    pub fn set_listener_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+        log::info!("OSC listener {}", if paused { "paused" } else { "resumed" });
+    }
+
+    pub fn is_listener_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    // Number of packets that failed to decode since the listener started
+    pub fn get_decode_error_count(&self) -> u64 {
+        self.decode_errors.load(Ordering::Relaxed)
+    }
+
+    // Process incoming OSC packet
+    fn process_osc_packet(&self, packet: OscPacket) -> Option<Parameter> {
+        match packet {
+            OscPacket::Message(msg) => {
+                self.record_observed_address(&msg.addr);
+                self.run_message_callbacks(&msg);
+                self.process_osc_message(msg)
+            }
+            OscPacket::Bundle(bundle) => {
+                let mut updated_param = None;
+                for packet in bundle.content {
+                    if let Some(param) = self.process_osc_packet(packet) {
+                        updated_param = Some(param);
+                    }
+                }
+                updated_param
+            }
+        }
+    }
+
+    // Register a callback for every inbound message, so in-app features can react to OSC
+    // without opening their own socket
+    #[allow(dead_code)] // Extension point; nothing in the app registers one yet
+    pub fn on_message<F>(&self, callback: F)
+    where
+        F: Fn(&OscMessage) + Send + Sync + 'static,
+    {
+        self.message_callbacks.lock().unwrap().0.push(Arc::new(callback));
+    }
+
+    // A panicking callback is logged and skipped so it can't take the listener down
+    fn run_message_callbacks(&self, msg: &OscMessage) {
+        let callbacks = self.message_callbacks.lock().unwrap().0.clone();
+        for callback in callbacks {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| callback(msg)));
+            if result.is_err() {
+                log::error!("OSC message callback panicked on {}", msg.addr);
+            }
+        }
+    }
+
+    // Process OSC message and extract parameter data
+    fn process_osc_message(&self, msg: OscMessage) -> Option<Parameter> {
+        // Only process avatar parameter messages
+        if let Some(param_name) = parameter_name_from_address(&msg.addr) {
+            let now = Instant::now();
+            let key = self.parameter_key(param_name);
+            self.update_rates
+                .lock()
+                .unwrap()
+                .entry(key)
+                .or_insert_with(|| UpdateRate::new(now))
+                .record(now);
+            
+            let (accepted, deadband) = {
+                let config = self.config.lock().unwrap();
+                (config.accepts_parameter(param_name), config.float_deadband)
+            };
+            if !accepted {
+                self.filtered_messages.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+            
+            // Colors stay off the scalar path: `value` is the red channel, `values` all of RGBA
+            if let Some(OscType::Color(color)) = msg.args.first() {
+                let rgba = color_to_values(color);
+                let mut param = Parameter::new(param_name, ParameterType::Color, rgba[0]);
+                param.values = Some(rgba);
+                
+                let stored = self.store_parameter(param);
+                self.notify_change_waiters(&stored);
+                return Some(stored);
+            }
+            
+            // The first argument decides the type; unsupported types are logged and counted
+            let Some((value, param_type)) = msg.args.first().and_then(osc_arg_value) else {
+                self.unsupported_messages.fetch_add(1, Ordering::Relaxed);
+                log::debug!(
+                    "Skipping {}: unsupported argument type {}",
+                    msg.addr,
+                    msg.args.first().map_or("(none)".to_string(), osc_type_name)
+                );
+                return None;
+            };
+            let mut param = Parameter::new(param_name, param_type, value);
+            
+            if deadband > 0.0 && msg.args.len() == 1 && self.within_deadband(&param, deadband) {
+                // Not stored, but VRChat did report it, which is what change waiters want to know
+                let has_waiters = !self.change_waiters.lock().unwrap().is_empty();
+                if let Some(stored) = has_waiters.then(|| self.get_parameter(param_name)).flatten() {
+                    self.notify_change_waiters(&stored);
+                }
+                return None;
+            }
+            
+            // Keep every numeric argument for multi-value messages (e.g. RGBA colors)
+            if msg.args.len() > 1 {
+                param.values = Some(msg.args.iter().filter_map(osc_arg_value).map(|(v, _)| v).collect());
+            }
+            
+            let stored = self.store_parameter(param);
+            self.notify_change_waiters(&stored);
+            return Some(stored);
+        }
+        
+        None
+    }
+}
+
+// 0-1 RGBA channels to an OSC color; missing channels are black and opaque
+fn color_from_values(values: &[f32]) -> OscColor {
+    let channel = |index: usize, default: f32| {
+        (values.get(index).copied().unwrap_or(default).clamp(0.0, 1.0) * 255.0).round() as u8
+    };
+    OscColor {
+        red: channel(0, 0.0),
+        green: channel(1, 0.0),
+        blue: channel(2, 0.0),
+        alpha: channel(3, 1.0),
+    }
+}
+
+fn color_to_values(color: &OscColor) -> Vec<f32> {
+    [color.red, color.green, color.blue, color.alpha]
+        .iter()
+        .map(|channel| *channel as f32 / 255.0)
+        .collect()
+}
+
+// Convert a single OSC argument into a parameter value and type
+fn osc_arg_value(arg: &OscType) -> Option<(f32, ParameterType)> {
+    match arg {
+        OscType::Float(f) => Some((*f, ParameterType::Float)),
+        OscType::Int(i) => Some((*i as f32, ParameterType::Int)),
+        OscType::Bool(b) => {
+            if *b {
+                Some((1.0, ParameterType::Bool))
+            } else {
+                Some((0.0, ParameterType::Bool))
+            }
+        }
+        OscType::Char(c) => Some((*c as u32 as f32, ParameterType::Int)), // Stored as its code point
+        _ => None, // Unsupported type
+    }
+}
+
+// Type name for logging skipped arguments
+fn osc_type_name(arg: &OscType) -> String {
+    match arg {
+        OscType::Nil => "Nil".to_string(),
+        OscType::Inf => "Infinitum".to_string(),
+        other => format!("{:?}", other).split('(').next().unwrap_or_default().to_string(),
+    }
+}
+
+// Milliseconds on a process-wide monotonic clock, so timestamps fit in an atomic
+fn monotonic_ms() -> u64 {
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    EPOCH.get_or_init(Instant::now).elapsed().as_millis() as u64
+}
+
+// Bind a non-blocking UDP socket for the listener
+fn bind_listener_socket(listen_addr: &str) -> Result<UdpSocket> {
+    let socket_addr = SocketAddr::from_str(listen_addr)?;
+    let socket = UdpSocket::bind(socket_addr)?;
+    socket.set_nonblocking(true)?;
+    Ok(socket)
+}
+
+// Errors worth retrying on the same socket; anything else means the socket is unusable
+fn is_transient_error(error: &std::io::Error) -> bool {
+    matches!(
+        error.kind(),
+        std::io::ErrorKind::Interrupted
+            | std::io::ErrorKind::TimedOut
+            // Windows reports ICMP port unreachable from earlier sends as a reset on UDP
+            | std::io::ErrorKind::ConnectionReset
+    )
+}
+
+// Parse a parameter type string coming from the frontend
+pub fn parse_parameter_type(param_type_str: &str) -> Result<ParameterType, String> {
+    match param_type_str {
+        "Float" => Ok(ParameterType::Float),
+        "Int" => Ok(ParameterType::Int),
+        "Bool" => Ok(ParameterType::Bool),
+        "Color" => Ok(ParameterType::Color),
+        _ => Err("Invalid parameter type".into()),
+    }
+}
+
+// Build the OSC address for a parameter; nested names keep their inner slashes
+pub fn parameter_address(param_name: &str) -> String {
+    format!("{}{}", AVATAR_PARAMETER_PREFIX, param_name)
+}
+
+// Inverse of parameter_address: strips the prefix exactly once, leaving nested segments intact
+pub fn parameter_name_from_address(addr: &str) -> Option<&str> {
+    addr.strip_prefix(AVATAR_PARAMETER_PREFIX).filter(|name| !name.is_empty())
+}
+
+// Send OSC message to VRChat
+pub fn send_parameter(param_name: &str, value: f32, param_type: &ParameterType, osc_state: &OscState) -> Result<()> {
+    ensure_scalar(param_name, param_type)?;
+    send_parameter_values(param_name, &[value], param_type, osc_state)
+}
+
+// Send a parameter carrying several values of the same type in one message
+pub fn send_parameter_values(param_name: &str, values: &[f32], param_type: &ParameterType, osc_state: &OscState) -> Result<()> {
+    if values.is_empty() {
+        return Err(anyhow!("No values to send for parameter: {}", param_name));
+    }
+    osc_state.ensure_writable(param_name)?;
+    
+    let bool_threshold = osc_state.get_config().bool_threshold;
+    let values: Vec<f32> = values.iter().map(|v| osc_state.outbound_value(param_name, *v, param_type)).collect();
+    let msg = build_parameter_message(osc_state.parameter_osc_address(param_name), &values, param_type, bool_threshold);
+    osc_state.log_debug_send(param_name, &msg.args);
+    
+    send_osc_message(msg, osc_state)
+}
+
+// Colors need every RGBA channel (see send_parameter_values); a single value would only set red
+pub fn ensure_scalar(param_name: &str, param_type: &ParameterType) -> Result<()> {
+    if *param_type == ParameterType::Color {
+        return Err(anyhow!("Parameter {} is a Color; send all RGBA channels instead of one value", param_name));
+    }
+    Ok(())
+}
+
+fn build_parameter_message(addr: String, values: &[f32], param_type: &ParameterType, bool_threshold: f32) -> OscMessage {
+    let args = match param_type {
+        ParameterType::Float => values.iter().map(|value| OscType::Float(*value)).collect(),
+        ParameterType::Int => values.iter().map(|value| OscType::Int(*value as i32)).collect(),
+        ParameterType::Bool => values.iter().map(|value| OscType::Bool(*value > bool_threshold)).collect(),
+        ParameterType::Color => vec![OscType::Color(color_from_values(values))],
+    };
+    
+    OscMessage { addr, args }
+}
+
+// Send several parameters in a single OSC bundle so VRChat applies them together. Bundles that
+// would encode larger than `max_packet_size` are split, so only each part is applied atomically
+pub fn send_parameter_bundle(entries: &[(String, f32, ParameterType)], osc_state: &OscState) -> Result<()> {
+    if entries.is_empty() {
+        return Err(anyhow!("No parameters to send"));
+    }
+    for (name, _, param_type) in entries {
+        osc_state.ensure_writable(name)?;
+        ensure_scalar(name, param_type)?;
+    }
+    
+    let config = osc_state.get_config();
+    let mut bundles: Vec<Vec<OscPacket>> = vec![Vec::new()];
+    let mut bundle_size = BUNDLE_HEADER_SIZE;
+    for (name, value, param_type) in entries {
+        let value = osc_state.outbound_value(name, *value, param_type);
+        let msg = build_parameter_message(osc_state.parameter_osc_address(name), &[value], param_type, config.bool_threshold);
+        osc_state.log_debug_send(name, &msg.args);
+        
+        let packet = OscPacket::Message(msg);
+        let size = BUNDLE_ELEMENT_PREFIX_SIZE + rosc::encoder::encode(&packet)?.len();
+        // A single oversized message still goes out, alone in its own bundle
+        if bundle_size + size > config.max_packet_size && !bundles[bundles.len() - 1].is_empty() {
+            bundles.push(Vec::new());
+            bundle_size = BUNDLE_HEADER_SIZE;
+        }
+        bundle_size += size;
+        bundles.last_mut().unwrap().push(packet);
+    }
+    
+    if bundles.len() > 1 {
+        log::debug!(
+            "Split {} parameters into {} bundles of at most {} bytes",
+            entries.len(),
+            bundles.len(),
+            config.max_packet_size
+        );
+    }
+    
+    for content in bundles {
+        // A timetag of 1 means "immediately"
+        let bundle = OscBundle {
+            timetag: OscTime { seconds: 0, fractional: 1 },
+            content,
+        };
+        send_osc_packet(OscPacket::Bundle(bundle), osc_state)?;
+    }
+    Ok(())
+}
+
+// Send a Bool parameter directly, without float coercion
+pub fn send_bool_parameter(param_name: &str, value: bool, osc_state: &OscState) -> Result<()> {
+    osc_state.ensure_writable(param_name)?;
+    
+    let msg = OscMessage {
+        addr: osc_state.parameter_osc_address(param_name),
+        args: vec![OscType::Bool(value)],
+    };
+    osc_state.log_debug_send(param_name, &msg.args);
+    
+    send_osc_message(msg, osc_state)
+}
+
+// Send a string argument to a parameter address, for string-backed receivers
+pub fn send_string_parameter(param_name: &str, value: &str, osc_state: &OscState) -> Result<()> {
+    osc_state.ensure_writable(param_name)?;
+    
+    let msg = OscMessage {
+        addr: osc_state.parameter_osc_address(param_name),
+        args: vec![OscType::String(value.to_string())],
+    };
+    osc_state.log_debug_send(param_name, &msg.args);
+    
+    send_osc_message(msg, osc_state)
+}
+
+// Drive a VRChat input (e.g. "Jump"). Axes send the value as-is; buttons are pressed when the
+// value is non-zero and released again shortly after
+pub fn send_input(input_name: &str, value: f32, osc_state: &OscState) -> Result<()> {
+    let addr = format!("{}{}", INPUT_PREFIX, input_name);
+    
+    if INPUT_AXES.contains(&input_name) {
+        return send_osc_message(OscMessage { addr, args: vec![OscType::Float(value.clamp(-1.0, 1.0))] }, osc_state);
+    }
+    
+    let pressed = value != 0.0;
+    send_osc_message(OscMessage { addr: addr.clone(), args: vec![OscType::Int(pressed as i32)] }, osc_state)?;
+    
+    if pressed {
+        let state = osc_state.clone();
+        thread::spawn(move || {
+            thread::sleep(INPUT_BUTTON_RELEASE);
+            if let Err(e) = send_osc_message(OscMessage { addr, args: vec![OscType::Int(0)] }, &state) {
+                log::error!("Failed to release input: {}", e);
+            }
+        });
+    }
+    
+    Ok(())
+}
+
+// Post a message to the chatbox immediately, without the notification sound
+pub fn send_chatbox(text: &str, osc_state: &OscState) -> Result<()> {
+    let msg = OscMessage {
+        addr: CHATBOX_INPUT_ADDRESS.to_string(),
+        args: vec![OscType::String(text.to_string()), OscType::Bool(true), OscType::Bool(false)],
+    };
+    
+    send_osc_message(msg, osc_state)
+}
+
+// Encode and send a single OSC message to the configured target
+pub fn send_osc_message(msg: OscMessage, osc_state: &OscState) -> Result<()> {
+    send_osc_packet(OscPacket::Message(msg), osc_state)
+}
+
+pub fn send_osc_packet(packet: OscPacket, osc_state: &OscState) -> Result<()> {
+    let config = osc_state.get_config();
+    let dest_addr = format!("{}:{}", config.target_address, config.target_port);
+    let dest_socket_addr = SocketAddr::from_str(&dest_addr)?;
+    
+    send_osc_packet_to(packet, dest_socket_addr, osc_state)
+}
+
+// Send to an explicit destination instead of the configured target
+pub fn send_osc_packet_to(packet: OscPacket, dest_socket_addr: SocketAddr, osc_state: &OscState) -> Result<()> {
+    let config = osc_state.get_config();
+    
+    if let Some(debug_network) = osc_state.debug_network() {
+        if debug_network.should_drop() {
+            log::debug!("Simulated drop of outgoing OSC packet");
+            return Ok(());
+        }
+        thread::sleep(Duration::from_millis(debug_network.send_delay_ms));
+    }
+    
+    let encoded = rosc::encoder::encode(&packet)?;
+    osc_state.with_send_socket(&config.send_bind_address, |socket| socket.send_to(&encoded, dest_socket_addr))?;
+    osc_state.send_bandwidth.record(encoded.len() as u64);
+    
+    Ok(())
+}
+
+// Tauri commands
+
+#[tauri::command]
+pub fn get_all_parameters(state: State<OscState>) -> Vec<Parameter> {
+    state.get_parameters()
+}
+
+#[tauri::command]
+pub fn rename_parameter(old: &str, new: &str, state: State<OscState>) -> Result<Parameter, String> {
+    state.rename_parameter(old, new)
+}
+
+#[tauri::command]
+pub fn remove_parameters_matching(pattern: &str, state: State<OscState>) -> Result<usize, String> {
+    state.remove_parameters_matching(pattern)
+}
+
+#[tauri::command]
+pub fn export_state(state: State<OscState>) -> Result<String, String> {
+    serde_json::to_string_pretty(&state.export_state())
+        .map_err(|e| format!("Failed to serialize state: {}", e))
+}
+
+#[tauri::command]
+pub fn import_state(json: &str, state: State<OscState>) -> Result<(), String> {
+    let snapshot: StateSnapshot = serde_json::from_str(json)
+        .map_err(|e| format!("Failed to parse state: {}", e))?;
+    state.import_state(snapshot)
+}
+
+#[tauri::command]
+pub fn export_config(path: &str, state: State<OscState>) -> Result<(), String> {
+    state.export_config(path)
+}
+
+#[tauri::command]
+pub fn import_config(path: &str, state: State<OscState>) -> Result<(), String> {
+    state.import_config(path)
+}
+
+#[tauri::command]
+pub fn get_meta_parameters(state: State<OscState>) -> Vec<Parameter> {
+    state.get_meta_parameters()
+}
+
+#[tauri::command]
+pub fn get_parameters_grouped(state: State<OscState>) -> Vec<ParameterGroup> {
+    state.get_parameters_grouped()
+}
+
+#[tauri::command]
+pub fn suggest_parameters(query: &str, limit: Option<usize>, state: State<OscState>) -> Vec<ParameterSuggestion> {
+    state.suggest_parameters(query, limit.unwrap_or(DEFAULT_SUGGESTION_LIMIT))
+}
+
+#[tauri::command]
+pub fn is_local(state: State<OscState>) -> bool {
+    state.is_local()
+}
+
+#[tauri::command]
+pub fn set_update_subscription(names: Vec<String>, window: Window, state: State<OscState>) {
+    state.set_update_subscription(window.label(), names)
+}
+
+#[tauri::command]
+pub fn set_send_debug_watch(name: Option<String>, state: State<OscState>) {
+    state.set_send_debug_watch(name)
+}
+
+#[tauri::command]
+pub fn get_parameter_schema() -> ParameterSchema {
+    ParameterSchema::describe()
+}
+
+// Both go through parse_parameter_type so the UI can never drift from what sends accept
+#[tauri::command]
+pub fn is_valid_parameter_type(s: &str) -> bool {
+    parse_parameter_type(s).is_ok()
+}
+
+#[tauri::command]
+pub fn list_parameter_types() -> Vec<String> {
+    ParameterType::ALL
+        .iter()
+        .map(|t| t.as_str().to_string())
+        .filter(|s| is_valid_parameter_type(s))
+        .collect()
+}
+
+// Re-announce the full parameter list, e.g. after a webview reconnects
+#[tauri::command]
+pub fn refresh_parameters(state: State<OscState>) {
+    state.emit_parameters();
+}
+
+// Send the full, sorted parameter list to the calling window so a newly opened window can
+// render before the next OSC message arrives. `Window::emit` would reach every window
+#[tauri::command]
+pub fn request_full_state(window: Window, state: State<OscState>) -> Result<(), String> {
+    window
+        .emit_to(window.label(), "parameter-updated", state.get_parameters())
+        .map_err(|e| format!("Failed to send parameters to window: {}", e))
+}
+
+#[tauri::command]
+pub fn set_parameter_value(
+    name: &str,
+    value: f32,
+    param_type_str: &str,
+    strict: Option<bool>,
+    state: State<OscState>,
+) -> Result<(), String> {
+    let param_type = parse_parameter_type(param_type_str)?;
+    
+    // Strict mode refuses parameters VRChat hasn't reported yet
+    if strict.unwrap_or(false) && state.get_parameter(name).is_none() {
+        return Err(format!("Failed to update parameter: Parameter not found: {}", name));
+    }
+    
+    // Check up front since the queued send reports errors only to the log
+    state.ensure_writable(name).map_err(|e| e.to_string())?;
+    ensure_scalar(name, &param_type).map_err(|e| e.to_string())?;
+    state.queue_parameter(name, value, param_type.clone());
+    state.set_or_insert_parameter(name, value, param_type);
+    
+    Ok(())
+}
+
+// Set a parameter by its `index` in the sorted parameter list, returning the resolved name
+#[tauri::command]
+pub fn set_parameter_by_index(index: usize, value: f32, state: State<OscState>) -> Result<String, String> {
+    state.set_parameter_by_index(index, value)
+        .map_err(|e| format!("Failed to update parameter: {}", e))
+}
+
+#[tauri::command]
+pub fn send_bool(name: &str, value: bool, state: State<OscState>) -> Result<(), String> {
+    send_bool_parameter(name, value, &state)
+        .map_err(|e| format!("Failed to send parameter: {}", e))?;
+    
+    // Keep our local copy in sync if we know the parameter
+    let _ = state.set_parameter(name, if value { 1.0 } else { 0.0 });
+    
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_float_deadband(deadband: f32, state: State<OscState>) -> Result<(), String> {
+    let config = OscConfig {
+        float_deadband: deadband,
+        ..state.get_config()
+    };
+    
+    state.update_config(config)
+        .map_err(|e| format!("Failed to update OSC config: {}", e))
+}
+
+#[tauri::command]
+pub fn set_max_packet_size(max_packet_size: usize, state: State<OscState>) -> Result<(), String> {
+    let config = OscConfig {
+        max_packet_size,
+        ..state.get_config()
+    };
+    
+    state.update_config(config)
+        .map_err(|e| format!("Failed to update OSC config: {}", e))
+}
+
+#[tauri::command]
+pub fn set_bool_threshold(threshold: f32, state: State<OscState>) -> Result<(), String> {
+    let config = OscConfig {
+        bool_threshold: threshold,
+        ..state.get_config()
+    };
+    
+    state.update_config(config)
+        .map_err(|e| format!("Failed to update OSC config: {}", e))
+}
+
+#[tauri::command]
+pub fn set_parameters(values: HashMap<String, f32>, state: State<OscState>) -> Vec<SetParameterResult> {
+    let mut results = Vec::new();
+    let mut entries = Vec::new();
+    
+    // Unknown and read-only parameters are reported instead of guessing a type
+    for (name, value) in values {
+        let error = match state.get_parameter(&name) {
+            None => Some(format!("Parameter not found: {}", name)),
+            Some(param) if !param.writable => Some(format!("Parameter is read-only: {}", name)),
+            Some(param) if param.parameter_type == ParameterType::Color => {
+                Some(format!("Parameter {} is a Color; set it with set_parameter_values", name))
+            }
+            Some(param) => {
+                entries.push((name.clone(), value, param.parameter_type));
+                None
+            }
+        };
+        if let Some(error) = error {
+            results.push(SetParameterResult { name, sent: false, error: Some(error) });
+        }
+    }
+    
+    if !entries.is_empty() {
+        let send_error = send_parameter_bundle(&entries, &state)
+            .err()
+            .map(|e| format!("Failed to send parameters: {}", e));
+        
+        for (name, value, _) in entries {
+            if send_error.is_none() {
+                let _ = state.set_parameter(&name, value);
+            }
+            results.push(SetParameterResult {
+                name,
+                sent: send_error.is_none(),
+                error: send_error.clone(),
+            });
+        }
+        state.emit_parameters();
+    }
+    
+    results.sort_by(|a, b| a.name.cmp(&b.name));
+    results
+}
+
+// One-off send to another endpoint, e.g. a second machine, leaving the config untouched
+#[tauri::command]
+pub fn send_parameter_to(
+    name: &str,
+    value: f32,
+    param_type: &str,
+    target_address: &str,
+    target_port: u16,
+    state: State<OscState>,
+) -> Result<(), String> {
+    let param_type = parse_parameter_type(param_type)?;
+    let target = format!("{}:{}", target_address, target_port);
+    let dest = SocketAddr::from_str(&target).map_err(|e| format!("Invalid target address {}: {}", target, e))?;
+    if target_port == 0 {
+        return Err("Target port must not be 0".to_string());
+    }
+    
+    state.ensure_writable(name).map_err(|e| e.to_string())?;
+    ensure_scalar(name, &param_type).map_err(|e| e.to_string())?;
+    let value = state.outbound_value(name, value, &param_type);
+    let msg = build_parameter_message(state.parameter_osc_address(name), &[value], &param_type, state.get_config().bool_threshold);
+    state.log_debug_send(name, &msg.args);
+    
+    send_osc_packet_to(OscPacket::Message(msg), dest, &state)
+        .map_err(|e| format!("Failed to send parameter: {}", e))
+}
+
+#[tauri::command]
+pub fn set_parameter_values(
+    name: &str,
+    values: Vec<f32>,
+    param_type_str: &str,
+    state: State<OscState>,
+) -> Result<(), String> {
+    let param_type = parse_parameter_type(param_type_str)?;
+    
+    send_parameter_values(name, &values, &param_type, &state)
+        .map_err(|e| format!("Failed to send parameter: {}", e))
+}
+
+#[tauri::command]
+pub fn set_queue_mode(name: &str, ordered: bool, state: State<OscState>) -> Result<(), String> {
+    let mode = if ordered { QueueMode::Ordered } else { QueueMode::Coalesce };
+    state.set_queue_mode(name, mode)
+}
+
+// Simulate an inbound parameter as if it had arrived over OSC (for UI testing)
+#[tauri::command]
+pub fn inject_parameter(
+    name: &str,
+    value: f32,
+    param_type_str: &str,
+    state: State<OscState>,
+) -> Result<(), String> {
+    let param_type = parse_parameter_type(param_type_str)?;
+    
+    state.update_parameter(Parameter::new(name, param_type, value));
+    
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_display_label(
+    name: &str,
+    label: Option<String>,
+    state: State<OscState>,
+) -> Result<(), String> {
+    state.set_display_label(name, label)
+}
+
+#[tauri::command]
+pub fn set_parameter_writable(name: &str, writable: bool, state: State<OscState>) -> Result<(), String> {
+    state.set_parameter_writable(name, writable)
+}
+
+#[tauri::command]
+pub fn set_parameter_curve(
+    name: &str,
+    curve: Option<ResponseCurve>,
+    state: State<OscState>,
+) -> Result<(), String> {
+    state.set_parameter_curve(name, curve)
+}
+
+#[tauri::command]
+pub fn set_parameter_send_curve(
+    name: &str,
+    curve: Option<SendCurve>,
+    state: State<OscState>,
+) -> Result<(), String> {
+    state.set_parameter_send_curve(name, curve)
+}
+
+#[tauri::command]
+pub fn resolve_send_type(
+    name: &str,
+    override_type: Option<String>,
+    state: State<OscState>,
+) -> Result<String, String> {
+    let override_type = override_type.as_deref().map(parse_parameter_type).transpose()?;
+    Ok(state.resolve_send_type(name, override_type, ParameterType::Float).as_str().to_string())
+}
+
+#[tauri::command]
+pub fn add_parameter_watch(
+    name: &str,
+    comparison: &str,
+    threshold: f32,
+    max: Option<f32>,
+    notify_os: Option<bool>,
+    state: State<OscState>,
+) -> Result<ParameterWatch, String> {
+    let condition = WatchCondition::parse(comparison, threshold, max)?;
+    state.add_parameter_watch(name, condition, notify_os.unwrap_or(false))
+}
+
+#[tauri::command]
+pub fn remove_parameter_watch(id: u64, state: State<OscState>) -> Result<bool, String> {
+    state.remove_parameter_watch(id)
+}
+
+#[tauri::command]
+pub fn get_parameter_watches(state: State<OscState>) -> Vec<ParameterWatch> {
+    state.get_parameter_watches()
+}
+
+// Send a harmless message and watch briefly for any inbound traffic
+#[tauri::command]
+pub async fn test_osc_send(
+    address: Option<String>,
+    timeout_ms: Option<u64>,
+    state: State<'_, OscState>,
+) -> Result<OscTestResult, String> {
+    let msg = OscMessage {
+        addr: address.unwrap_or_else(|| DEFAULT_PING_ADDRESS.to_string()),
+        args: vec![OscType::Bool(true)],
+    };
+    let timeout = Duration::from_millis(timeout_ms.unwrap_or(DEFAULT_PING_TIMEOUT_MS));
+    
+    let packets_before = state.get_packets_received();
+    let send_result = send_osc_message(msg, &state);
+    let listener_running = state.is_listener_running();
+    
+    let mut response_observed = false;
+    if send_result.is_ok() && listener_running {
+        let started = Instant::now();
+        while started.elapsed() < timeout {
+            if state.get_packets_received() > packets_before {
+                response_observed = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }
+    
+    Ok(OscTestResult {
+        send_ok: send_result.is_ok(),
+        send_error: send_result.err().map(|e| e.to_string()),
+        listener_running,
+        response_observed,
+    })
+}
+
+#[tauri::command]
+pub async fn await_parameter_change(
+    name: String,
+    timeout_ms: u64,
+    state: State<'_, OscState>,
+) -> Result<Option<Parameter>, String> {
+    Ok(state.await_parameter_change(&name, Duration::from_millis(timeout_ms)).await)
+}
+
+#[tauri::command]
+pub async fn set_and_confirm(
+    name: String,
+    value: f32,
+    param_type: String,
+    timeout_ms: u64,
+    tolerance: Option<f32>,
+    state: State<'_, OscState>,
+) -> Result<ConfirmResult, String> {
+    let param_type = parse_parameter_type(&param_type)?;
+    
+    state
+        .set_and_confirm(
+            &name,
+            value,
+            param_type,
+            Duration::from_millis(timeout_ms),
+            tolerance.unwrap_or(DEFAULT_CONFIRM_TOLERANCE),
+        )
+        .await
+        .map_err(|e| format!("Failed to send parameter: {}", e))
+}
+
+#[tauri::command]
+pub async fn query_parameter(
+    name: String,
+    timeout_ms: u64,
+    state: State<'_, OscState>,
+) -> Result<ParameterQueryResult, String> {
+    Ok(state.query_parameter(&name, Duration::from_millis(timeout_ms)).await)
+}
+
+#[tauri::command]
+pub fn update_osc_config(
+    target_address: &str,
+    target_port: u16,
+    listen_address: &str,
+    listen_port: u16,
+    state: State<OscState>,
+) -> Result<(), String> {
+    let config = OscConfig {
+        target_address: target_address.to_string(),
+        target_port,
+        listen_address: listen_address.to_string(),
+        listen_port,
+        ..state.get_config()
+    };
+    
+    state.update_config(config)
+        .map_err(|e| format!("Failed to update OSC config: {}", e))
+}
+
+#[tauri::command]
+pub fn get_osc_config(state: State<OscState>) -> OscConfig {
+    state.get_config()
+}
+
+#[tauri::command]
+pub fn set_send_bind_address(address: &str, state: State<OscState>) -> Result<(), String> {
+    let config = OscConfig {
+        send_bind_address: address.to_string(),
+        ..state.get_config()
+    };
+    
+    state.update_config(config)
+        .map_err(|e| format!("Failed to update OSC config: {}", e))
+}
+
+#[tauri::command]
+pub fn set_parameter_filters(
+    accept_patterns: Vec<String>,
+    ignore_patterns: Vec<String>,
+    state: State<OscState>,
+) -> Result<(), String> {
+    let config = OscConfig {
+        accept_patterns,
+        ignore_patterns,
+        ..state.get_config()
+    };
+    
+    state.update_config(config)
+        .map_err(|e| format!("Failed to update OSC config: {}", e))
+}
+
+#[tauri::command]
+pub fn seconds_since_last_packet(state: State<OscState>) -> Option<f64> {
+    state.seconds_since_last_packet()
+}
+
+#[tauri::command]
+pub fn get_filtered_message_count(state: State<OscState>) -> u64 {
+    state.get_filtered_message_count()
+}
+
+#[tauri::command]
+pub fn get_unsupported_message_count(state: State<OscState>) -> u64 {
+    state.get_unsupported_message_count()
+}
+
+#[tauri::command]
+pub fn set_allow_loopback(allow: bool, state: State<OscState>) -> Result<(), String> {
+    let config = OscConfig {
+        allow_loopback: allow,
+        ..state.get_config()
+    };
+    
+    state.update_config(config)
+        .map_err(|e| format!("Failed to update OSC config: {}", e))
+}
+
+#[tauri::command]
+pub fn reset_osc_config(state: State<OscState>) -> Result<OscConfig, String> {
+    state.update_config(OscConfig::default())
+        .map_err(|e| format!("Failed to reset OSC config: {}", e))?;
+    
+    Ok(state.get_config())
+}
+
+#[tauri::command]
+pub fn set_decode_error_threshold(threshold: u64, state: State<OscState>) -> Result<(), String> {
+    let config = OscConfig {
+        decode_error_threshold: threshold,
+        ..state.get_config()
+    };
+    
+    state.update_config(config)
+        .map_err(|e| format!("Failed to update OSC config: {}", e))
+}
+
+#[tauri::command]
+pub fn set_debug_network(debug_network: Option<DebugNetworkConfig>, state: State<OscState>) -> Result<(), String> {
+    if debug_network.is_some() && !cfg!(debug_assertions) {
+        return Err("Network simulation is only available in debug builds".into());
+    }
+    
+    let config = OscConfig {
+        debug_network,
+        ..state.get_config()
+    };
+    
+    state.update_config(config)
+        .map_err(|e| format!("Failed to update OSC config: {}", e))
+}
+
+#[tauri::command]
+pub fn set_send_rate_limit(hz: u32, state: State<OscState>) -> Result<u32, String> {
+    let config = OscConfig {
+        send_rate_hz: hz,
+        ..state.get_config()
+    };
+    
+    state.update_config(config)
+        .map_err(|e| format!("Failed to update OSC config: {}", e))?;
+    
+    Ok(state.get_config().send_rate_hz)
+}
+
+#[tauri::command]
+pub fn start_send_burst(multiplier: f32, duration_ms: u64, state: State<OscState>) -> Result<u64, String> {
+    state.start_burst(multiplier, Duration::from_millis(duration_ms))
+}
+
+#[tauri::command]
+pub fn end_send_burst(id: u64, state: State<OscState>) {
+    state.end_burst(id)
+}
+
+#[tauri::command]
+pub fn set_event_debounce_ms(ms: u64, state: State<OscState>) -> Result<u64, String> {
+    let config = OscConfig {
+        event_debounce_ms: ms,
+        ..state.get_config()
+    };
+    
+    state.update_config(config)
+        .map_err(|e| format!("Failed to update OSC config: {}", e))?;
+    
+    Ok(state.get_config().event_debounce_ms)
+}
+
+#[tauri::command]
+pub fn get_parameter_rates(state: State<OscState>) -> HashMap<String, u32> {
+    state.get_parameter_rates()
+}
+
+#[tauri::command]
+pub fn get_observed_addresses(state: State<OscState>) -> Vec<ObservedAddress> {
+    state.get_observed_addresses()
+}
+
+#[tauri::command]
+pub fn get_send_bandwidth(state: State<OscState>) -> BandwidthStats {
+    state.get_send_bandwidth()
+}
+
+#[tauri::command]
+pub fn get_emit_failure_count(state: State<OscState>) -> u64 {
+    state.get_emit_failure_count()
+}
+
+#[tauri::command]
+pub fn get_decode_error_count(state: State<OscState>) -> u64 {
+    state.get_decode_error_count()
+}
+
+#[tauri::command]
+pub fn get_listen_address(state: State<OscState>) -> Option<String> {
+    state.get_bound_address().map(|addr| addr.to_string())
+}
+
+#[tauri::command]
+pub fn pause_listener(state: State<OscState>) {
+    state.set_listener_paused(true)
+}
+
+#[tauri::command]
+pub fn resume_listener(state: State<OscState>) {
+    state.set_listener_paused(false)
+}
+
+#[tauri::command]
+pub fn is_listener_paused(state: State<OscState>) -> bool {
+    state.is_listener_paused()
+}
+
+#[tauri::command]
+pub fn restart_osc_listener(state: State<OscState>) -> Result<(), String> {
+    state.stop_listener()
+        .map_err(|e| format!("Failed to stop OSC listener: {}", e))?;
+    
+    state.start_listener()
+        .map_err(|e| format!("Failed to start OSC listener: {}", e))
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    // Fresh temporary directory for a test's settings files
+    pub(crate) fn temp_dir(prefix: &str) -> PathBuf {
+        static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+        std::env::temp_dir().join(format!(
+            "vrcparam-{}-{}-{}",
+            prefix,
+            std::process::id(),
+            NEXT_DIR.fetch_add(1, Ordering::Relaxed)
+        ))
+    }
+
+    // State whose settings files go to a fresh temporary directory
+    pub(crate) fn test_state() -> OscState {
+        let state = OscState::new();
+        *state.data_dir.lock().unwrap() = Some(temp_dir("test"));
+        state
+    }
+
+    // Point the state's target at a fresh loopback socket and return it for reading sends
+    pub(crate) fn capture_sends(state: &OscState) -> UdpSocket {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver.set_read_timeout(Some(Duration::from_secs(2))).unwrap();
+        let mut config = state.config.lock().unwrap();
+        config.target_address = "127.0.0.1".to_string();
+        config.target_port = receiver.local_addr().unwrap().port();
+        receiver
+    }
+
+    // Test state whose sends go to the returned loopback socket
+    pub(crate) fn capture_osc() -> (OscState, UdpSocket) {
+        let state = test_state();
+        let receiver = capture_sends(&state);
+        (state, receiver)
+    }
+
+    pub(crate) fn recv_packet(receiver: &UdpSocket) -> OscPacket {
+        let mut buf = [0u8; 65536];
+        let (size, _) = receiver.recv_from(&mut buf).unwrap();
+        rosc::decoder::decode_udp(&buf[..size]).unwrap().1
+    }
+
+    pub(crate) fn recv_message(receiver: &UdpSocket) -> OscMessage {
+        match recv_packet(receiver) {
+            OscPacket::Message(msg) => msg,
+            other => panic!("Expected a message, got {:?}", other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::*;
+    use super::*;
+
+    fn labels(labels: &[&str]) -> Vec<String> {
+        labels.iter().map(|label| label.to_string()).collect()
+    }
+
+    // parameter_emits as (label, event, parameter names)
+    fn emitted(state: &OscState, open: &[String]) -> Vec<(String, &'static str, Vec<String>)> {
+        state
+            .parameter_emits(open)
+            .into_iter()
+            .map(|(label, event, params)| (label, event, params.into_iter().map(|p| p.name).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn subscription_only_reports_subscribed_changes() {
+        let state = test_state();
+        let open = labels(&["main", "overlay"]);
+        state.update_parameter(Parameter::new("A", ParameterType::Float, 0.0));
+        state.update_parameter(Parameter::new("B", ParameterType::Float, 0.0));
+        
+        // A new subscriber starts from the current values
+        state.set_update_subscription("overlay", vec!["A".to_string()]);
+        assert_eq!(emitted(&state, &open), vec![
+            ("main".to_string(), "parameter-updated", labels(&["A", "B"])),
+            ("overlay".to_string(), "subscribed-parameters-updated", labels(&["A"])),
+        ]);
+        
+        // The overlay never gets the full list, and nothing at all for unsubscribed changes
+        state.store_parameter(Parameter::new("B", ParameterType::Float, 0.5));
+        assert_eq!(emitted(&state, &open), vec![
+            ("main".to_string(), "parameter-updated", labels(&["A", "B"])),
+        ]);
+        
+        state.store_parameter(Parameter::new("A", ParameterType::Float, 0.5));
+        let emits = state.parameter_emits(&open);
+        assert_eq!(emits.len(), 2);
+        assert_eq!(emits[1].0, "overlay");
+        assert_eq!(emits[1].2.len(), 1);
+        assert_eq!(emits[1].2[0].value, 0.5);
+    }
+
+    #[test]
+    fn empty_subscription_restores_the_full_list() {
+        let state = test_state();
+        let open = labels(&["overlay"]);
+        state.update_parameter(Parameter::new("A", ParameterType::Float, 0.0));
+        state.set_update_subscription("overlay", vec!["A".to_string()]);
+        state.parameter_emits(&open);
+        
+        state.set_update_subscription("overlay", Vec::new());
+        assert_eq!(emitted(&state, &open), vec![
+            ("overlay".to_string(), "parameter-updated", labels(&["A"])),
+        ]);
+    }
+
+    #[test]
+    fn closed_windows_lose_their_subscription() {
+        let state = test_state();
+        state.set_update_subscription("overlay", vec!["A".to_string()]);
+        
+        state.parameter_emits(&labels(&["main"]));
+        assert!(state.update_subscriptions.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn stop_detaches_a_stuck_listener_within_the_timeout() {
+        let state = test_state();
+        state.config.lock().unwrap().stop_timeout_ms = 100;
+        
+        // A listener wedged in a call that ignores its stop flag for a while
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = thread::spawn(|| thread::sleep(Duration::from_secs(2)));
+        *state.listener_thread.lock().unwrap() = Some(ListenerThread { handle, stop: stop.clone() });
+        *state.running.lock().unwrap() = true;
+        
+        let started = Instant::now();
+        state.stop_listener().unwrap();
+        assert!(started.elapsed() < Duration::from_millis(1000));
+        assert!(stop.load(Ordering::Relaxed));
+        assert!(!state.is_listener_running());
+    }
+
+    #[test]
+    fn restart_gives_the_new_listener_its_own_stop_flag() {
+        let state = test_state();
+        state.config.lock().unwrap().listen_address = "127.0.0.1".to_string();
+        state.config.lock().unwrap().listen_port = 0;
+        
+        state.start_listener().unwrap();
+        let old_stop = state.listener_thread.lock().unwrap().as_ref().unwrap().stop.clone();
+        state.stop_listener().unwrap();
+        state.start_listener().unwrap();
+        
+        let new_stop = state.listener_thread.lock().unwrap().as_ref().unwrap().stop.clone();
+        assert!(old_stop.load(Ordering::Relaxed));
+        assert!(!new_stop.load(Ordering::Relaxed));
+        assert!(!Arc::ptr_eq(&old_stop, &new_stop));
+        state.stop_listener().unwrap();
+    }
+
+    #[test]
+    fn burst_guard_raises_the_rate_and_reverts_on_drop() {
+        let state = test_state();
+        state.config.lock().unwrap().send_rate_hz = 30;
+        
+        {
+            let _burst = state.with_burst(4.0, Duration::from_secs(60)).unwrap();
+            assert_eq!(state.effective_send_rate_hz(), 120.0);
+        }
+        assert_eq!(state.effective_send_rate_hz(), 30.0);
+    }
+
+    #[test]
+    fn burst_reverts_once_expired() {
+        let state = test_state();
+        state.config.lock().unwrap().send_rate_hz = 30;
+        
+        let _burst = state.with_burst(4.0, Duration::from_millis(20)).unwrap();
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(state.effective_send_rate_hz(), 30.0);
+    }
+
+    #[test]
+    fn burst_rate_is_capped() {
+        let state = test_state();
+        state.config.lock().unwrap().send_rate_hz = 100;
+        
+        let _burst = state.with_burst(10.0, Duration::from_secs(60)).unwrap();
+        assert_eq!(state.effective_send_rate_hz(), MAX_SEND_RATE_HZ as f64);
+    }
+
+    fn case_insensitive_state() -> OscState {
+        let state = test_state();
+        state.config.lock().unwrap().case_insensitive_names = true;
+        state
+    }
+
+    #[test]
+    fn case_insensitive_settings_apply_to_any_spelling() {
+        let state = case_insensitive_state();
+        state.set_display_label("Hat", Some("My hat".to_string())).unwrap();
+        state.set_parameter_writable("HAT", false).unwrap();
+        
+        let stored = state.store_parameter(Parameter::new("hat", ParameterType::Float, 0.0));
+        assert_eq!(stored.display_name.as_deref(), Some("My hat"));
+        assert!(!stored.writable);
+    }
+
+    #[test]
+    fn case_insensitive_watches_match_any_spelling() {
+        let state = case_insensitive_state();
+        state.add_parameter_watch("HAT", WatchCondition::GreaterThan { threshold: 0.5 }, false).unwrap();
+        
+        state.store_parameter(Parameter::new("hat", ParameterType::Float, 1.0));
+        assert!(state.get_parameter_watches()[0].triggered);
+    }
+
+    #[test]
+    fn case_sensitive_settings_only_apply_to_exact_name() {
+        let state = test_state();
+        state.set_display_label("Hat", Some("My hat".to_string())).unwrap();
+        state.add_parameter_watch("Hat", WatchCondition::GreaterThan { threshold: 0.5 }, false).unwrap();
+        
+        let stored = state.store_parameter(Parameter::new("hat", ParameterType::Float, 1.0));
+        assert_eq!(stored.display_name, None);
+        assert!(!state.get_parameter_watches()[0].triggered);
+        
+        let stored = state.store_parameter(Parameter::new("Hat", ParameterType::Float, 1.0));
+        assert_eq!(stored.display_name.as_deref(), Some("My hat"));
+        assert!(state.get_parameter_watches()[0].triggered);
+    }
+
+    #[test]
+    fn switching_case_mode_rekeys_settings() {
+        let state = test_state();
+        state.store_parameter(Parameter::new("Hat", ParameterType::Float, 0.0));
+        state.set_display_label("Hat", Some("My hat".to_string())).unwrap();
+        
+        let config = OscConfig { case_insensitive_names: true, ..state.get_config() };
+        state.update_config(config).unwrap();
+        assert_eq!(state.labels.lock().unwrap().get("hat").map(String::as_str), Some("My hat"));
+        let stored = state.store_parameter(Parameter::new("HAT", ParameterType::Float, 0.0));
+        assert_eq!(stored.display_name.as_deref(), Some("My hat"));
+        
+        let config = OscConfig { case_insensitive_names: false, ..state.get_config() };
+        state.update_config(config).unwrap();
+        assert_eq!(state.labels.lock().unwrap().get("Hat").map(String::as_str), Some("My hat"));
+    }
+
+    // State whose sends go to its own listener on a free loopback port
+    fn loopback_state() -> OscState {
+        let state = test_state();
+        {
+            let mut config = state.config.lock().unwrap();
+            config.listen_address = "127.0.0.1".to_string();
+            config.listen_port = 0;
+            config.target_address = "127.0.0.1".to_string();
+            config.allow_loopback = true;
+        }
+        state.start_listener().unwrap();
+        let port = state.get_bound_address().unwrap().port();
+        state.config.lock().unwrap().target_port = port;
+        state
+    }
+
+    #[tokio::test]
+    async fn set_and_confirm_resolves_on_loopback_echo() {
+        let state = loopback_state();
+        state.spawn_workers();
+        
+        let result = state
+            .set_and_confirm("Hat", 0.75, ParameterType::Float, Duration::from_secs(2), DEFAULT_CONFIRM_TOLERANCE)
+            .await
+            .unwrap();
+        assert!(result.confirmed);
+        assert_eq!(result.reported.unwrap().value, 0.75);
+        assert!(state.change_waiters.lock().unwrap().is_empty());
+        state.stop_listener().unwrap();
+    }
+
+    #[tokio::test]
+    async fn set_and_confirm_on_read_only_leaves_no_waiter() {
+        let state = test_state();
+        state.store_parameter(Parameter::new("Hat", ParameterType::Float, 0.0));
+        state.set_parameter_writable("Hat", false).unwrap();
+        
+        let result = state
+            .set_and_confirm("Hat", 1.0, ParameterType::Float, Duration::from_millis(50), DEFAULT_CONFIRM_TOLERANCE)
+            .await;
+        assert!(result.is_err());
+        assert!(state.change_waiters.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn timed_out_waiter_is_removed() {
+        let state = test_state();
+        assert!(state.await_parameter_change("Hat", Duration::from_millis(10)).await.is_none());
+        assert!(state.change_waiters.lock().unwrap().is_empty());
+    }
+
+    fn avatar_config(json: &str) -> AvatarConfig {
+        serde_json::from_str(json).unwrap()
+    }
+
+    const READ_ONLY_CONFIG: &str = r#"{
+        "id": "avtr_test",
+        "name": "Test",
+        "parameters": [
+            {
+                "name": "Hat",
+                "input": { "address": "/avatar/parameters/Hat", "type": "Bool" },
+                "output": { "address": "/avatar/parameters/Hat", "type": "Bool" }
+            },
+            {
+                "name": "VelocityX",
+                "output": { "address": "/avatar/parameters/VelocityX", "type": "Float" }
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn imported_parameters_without_input_are_read_only() {
+        let state = test_state();
+        state.store_parameter(Parameter::new("VelocityX", ParameterType::Float, 0.0));
+        state.set_avatar_config(Some(avatar_config(READ_ONLY_CONFIG))).unwrap();
+        
+        assert!(!state.get_parameter("VelocityX").unwrap().writable);
+        assert!(state.ensure_writable("VelocityX").is_err());
+        assert!(state.ensure_writable("Hat").is_ok());
+    }
+
+    #[test]
+    fn undiscovered_read_only_parameter_is_rejected_and_stored_read_only() {
+        let state = test_state();
+        state.set_avatar_config(Some(avatar_config(READ_ONLY_CONFIG))).unwrap();
+        assert!(state.ensure_writable("VelocityX").is_err());
+        
+        let stored = state.store_parameter(Parameter::new("VelocityX", ParameterType::Float, 0.5));
+        assert!(!stored.writable);
+        let stored = state.store_parameter(Parameter::new("Hat", ParameterType::Bool, 1.0));
+        assert!(stored.writable);
+    }
+
+    #[test]
+    fn manual_override_beats_imported_access() {
+        let state = test_state();
+        state.set_parameter_writable("VelocityX", true).unwrap();
+        state.set_avatar_config(Some(avatar_config(READ_ONLY_CONFIG))).unwrap();
+        
+        assert!(state.ensure_writable("VelocityX").is_ok());
+        let stored = state.store_parameter(Parameter::new("VelocityX", ParameterType::Float, 0.5));
+        assert!(stored.writable);
+    }
+
+    #[test]
+    fn rename_carries_watches_queue_modes_and_rates() {
+        let state = test_state();
+        state.store_parameter(Parameter::new("Old", ParameterType::Float, 0.0));
+        state.add_parameter_watch("Old", WatchCondition::GreaterThan { threshold: 0.5 }, false).unwrap();
+        state.set_queue_mode("Old", QueueMode::Ordered).unwrap();
+        state.update_rates.lock().unwrap().insert("Old".to_string(), UpdateRate::new(Instant::now()));
+        
+        state.rename_parameter("Old", "New").unwrap();
+        
+        assert_eq!(state.get_parameter_watches()[0].parameter_name, "New");
+        assert_eq!(state.queue_modes.lock().unwrap().get("New"), Some(&QueueMode::Ordered));
+        assert!(!state.queue_modes.lock().unwrap().contains_key("Old"));
+        assert!(state.update_rates.lock().unwrap().contains_key("New"));
+        assert!(!state.update_rates.lock().unwrap().contains_key("Old"));
+    }
+
+    #[test]
+    fn rename_onto_existing_parameter_is_rejected() {
+        let state = test_state();
+        state.store_parameter(Parameter::new("A", ParameterType::Float, 0.0));
+        state.store_parameter(Parameter::new("B", ParameterType::Float, 0.0));
+        state.set_display_label("A", Some("First".to_string())).unwrap();
+        state.set_queue_mode("A", QueueMode::Ordered).unwrap();
+        
+        assert!(state.rename_parameter("A", "B").is_err());
+        assert!(state.get_parameter("A").is_some());
+        assert_eq!(state.labels.lock().unwrap().get("A").map(String::as_str), Some("First"));
+        assert_eq!(state.queue_modes.lock().unwrap().get("A"), Some(&QueueMode::Ordered));
+    }
+
+    #[test]
+    fn is_local_waits_for_vrchat_to_report_it() {
+        let state = test_state();
+        assert!(!state.is_local());
+        
+        state.store_parameter(Parameter::new("IsLocal", ParameterType::Bool, 0.0));
+        assert!(!state.is_local());
+        
+        state.store_parameter(Parameter::new("IsLocal", ParameterType::Bool, 1.0));
+        assert!(state.is_local());
+    }
+
+    #[test]
+    fn built_in_parameters_are_flagged_meta() {
+        let state = test_state();
+        state.store_parameter(Parameter::new("IsLocal", ParameterType::Bool, 1.0));
+        state.store_parameter(Parameter::new("Hat", ParameterType::Bool, 1.0));
+        
+        let meta: Vec<String> = state.get_meta_parameters().into_iter().map(|p| p.name).collect();
+        assert_eq!(meta, vec!["IsLocal"]);
+        assert!(!state.get_parameter("Hat").unwrap().is_meta);
+    }
+
+    #[test]
+    fn schema_lists_every_serialized_field() {
+        let mut param = Parameter::new("Hat", ParameterType::Float, 0.5);
+        param.display_name = Some("Hat".to_string());
+        param.shaped_value = Some(0.5);
+        param.values = Some(vec![0.5]);
+        param.osc_address = Some("/custom/hat".to_string());
+        param.index = Some(0);
+        param.ui_value = Some(0.5);
+        param.menu_path = Some("Outfits".to_string());
+        
+        // Every optional field is set, so a field missing from the schema shows up here
+        let json = serde_json::to_value(&param).unwrap();
+        let fields: Vec<&str> = ParameterSchema::describe().fields.iter().map(|f| f.name).collect();
+        for key in json.as_object().unwrap().keys() {
+            assert!(fields.contains(&key.as_str()), "{} is missing from the schema", key);
+        }
+        assert_eq!(fields.len(), json.as_object().unwrap().len());
+    }
+
+    #[test]
+    fn sends_go_to_the_parameter_osc_address() {
+        let state = test_state();
+        let receiver = capture_sends(&state);
+        let mut param = Parameter::new("Hat", ParameterType::Float, 0.0);
+        param.osc_address = Some("/custom/hat".to_string());
+        state.store_parameter(param);
+        
+        send_parameter("Hat", 0.5, &ParameterType::Float, &state).unwrap();
+        let msg = recv_message(&receiver);
+        assert_eq!(msg.addr, "/custom/hat");
+        assert_eq!(msg.args, vec![OscType::Float(0.5)]);
+    }
+
+    #[test]
+    fn custom_address_survives_updates_without_one() {
+        let state = test_state();
+        let receiver = capture_sends(&state);
+        let mut param = Parameter::new("Hat", ParameterType::Float, 0.0);
+        param.osc_address = Some("/custom/hat".to_string());
+        state.store_parameter(param);
+        state.store_parameter(Parameter::new("Hat", ParameterType::Float, 1.0));
+        
+        send_parameter("Hat", 0.5, &ParameterType::Float, &state).unwrap();
+        assert_eq!(recv_message(&receiver).addr, "/custom/hat");
+    }
+
+    #[test]
+    fn sends_default_to_the_avatar_parameter_address() {
+        let state = test_state();
+        let receiver = capture_sends(&state);
+        
+        send_parameter("Hat", 0.5, &ParameterType::Float, &state).unwrap();
+        assert_eq!(recv_message(&receiver).addr, "/avatar/parameters/Hat");
+    }
+
+    fn queued_names(state: &OscState) -> Vec<String> {
+        state.send_queue.0.lock().unwrap().iter().map(|q| q.name.clone()).collect()
+    }
+
+    #[test]
+    fn set_by_index_follows_sorted_order() {
+        let state = test_state();
+        for name in ["C", "A", "B"] {
+            state.store_parameter(Parameter::new(name, ParameterType::Float, 0.0));
+        }
+        
+        assert_eq!(state.get_parameter_by_index(1).unwrap().index, Some(1));
+        assert_eq!(state.set_parameter_by_index(1, 0.5).unwrap(), "B");
+        assert_eq!(state.get_parameter("B").unwrap().value, 0.5);
+        assert_eq!(queued_names(&state), vec!["B"]);
+    }
+
+    #[test]
+    fn set_by_index_rejects_out_of_range_and_read_only() {
+        let state = test_state();
+        state.store_parameter(Parameter::new("A", ParameterType::Float, 0.0));
+        state.store_parameter(Parameter::new("B", ParameterType::Int, 0.0));
+        state.store_parameter(Parameter::new("C", ParameterType::Bool, 0.0));
+        state.set_parameter_writable("A", false).unwrap();
+        
+        assert!(state.set_parameter_by_index(3, 0.5).is_err());
+        assert!(state.set_parameter_by_index(0, 0.5).is_err());
+        assert!(state.set_parameter_by_index(1, 300.0).is_err());
+        assert!(state.set_parameter_by_index(2, 0.5).is_err());
+        assert!(queued_names(&state).is_empty());
+        assert_eq!(state.get_parameter("B").unwrap().value, 0.0);
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-5, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn linear_send_curve_is_identity() {
+        for value in [-1.0, -0.3, 0.0, 0.3, 1.0] {
+            assert_eq!(SendCurve::Linear.apply(value), value);
+        }
+    }
+
+    #[test]
+    fn exponential_send_curve_favours_the_low_end() {
+        let curve = SendCurve::Exponential;
+        assert_close(curve.apply(0.0), 0.0);
+        assert_close(curve.apply(1.0), 1.0);
+        assert!(curve.apply(0.5) < 0.5);
+        assert_close(curve.apply(-0.5), -curve.apply(0.5));
+        assert_close(curve.apply(2.0), 1.0);
+    }
+
+    #[test]
+    fn logarithmic_send_curve_favours_the_high_end() {
+        let curve = SendCurve::Logarithmic;
+        assert_close(curve.apply(0.0), 0.0);
+        assert_close(curve.apply(1.0), 1.0);
+        assert!(curve.apply(0.5) > 0.5);
+        assert_close(curve.apply(-1.0), -1.0);
+    }
+
+    #[test]
+    fn ui_value_is_kept_until_vrchat_reports_something_else() {
+        let state = test_state();
+        state.set_parameter_send_curve("Light", Some(SendCurve::Exponential)).unwrap();
+        state.set_or_insert_parameter("Light", 0.5, ParameterType::Float);
+        
+        let param = state.get_parameter("Light").unwrap();
+        let sent = SendCurve::Exponential.apply(0.5);
+        assert_close(param.value, sent);
+        assert_eq!(param.ui_value, Some(0.5));
+        
+        // VRChat echoing the shaped value keeps the slider where the user left it
+        let echoed = state.store_parameter(Parameter::new("Light", ParameterType::Float, sent));
+        assert_eq!(echoed.ui_value, Some(0.5));
+        
+        let changed = state.store_parameter(Parameter::new("Light", ParameterType::Float, 0.9));
+        assert_eq!(changed.ui_value, None);
+    }
+
+    const MENU_CONFIG: &str = r#"{
+        "id": "avtr_test",
+        "name": "Test",
+        "parameters": [],
+        "menu": {
+            "name": "Root",
+            "controls": [
+                { "name": "Sparkles", "parameter": "Sparkles" },
+                {
+                    "name": "Outfits",
+                    "subMenu": {
+                        "name": "Outfits",
+                        "controls": [
+                            { "name": "Cap", "parameter": "Hat/Cap" }
+                        ]
+                    }
+                }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn parameters_group_by_menu_path_then_name_prefix() {
+        let state = test_state();
+        state.store_parameter(Parameter::new("Hat/Cap", ParameterType::Bool, 0.0));
+        state.set_avatar_config(Some(avatar_config(MENU_CONFIG))).unwrap();
+        for name in ["Sparkles", "Face/Smile"] {
+            state.store_parameter(Parameter::new(name, ParameterType::Bool, 0.0));
+        }
+        
+        assert_eq!(state.get_parameter("Hat/Cap").unwrap().menu_path.as_deref(), Some("Outfits"));
+        assert_eq!(state.get_parameter("Sparkles").unwrap().menu_path, None);
+        
+        let groups: Vec<(String, bool, Vec<String>)> = state
+            .get_parameters_grouped()
+            .into_iter()
+            .map(|g| (g.name, g.from_menu, g.parameters.into_iter().map(|p| p.name).collect()))
+            .collect();
+        assert!(groups.contains(&("Outfits".to_string(), true, vec!["Hat/Cap".to_string()])));
+        assert!(groups.contains(&("Face".to_string(), false, vec!["Face/Smile".to_string()])));
+        assert!(groups.contains(&(String::new(), false, vec!["Sparkles".to_string()])));
+    }
+
+    fn io_error(kind: std::io::ErrorKind) -> std::io::Error {
+        std::io::Error::new(kind, "test")
+    }
+
+    #[test]
+    fn backoff_doubles_then_rebinds_after_repeated_errors() {
+        let mut backoff = ListenerBackoff::new();
+        let timed_out = io_error(std::io::ErrorKind::TimedOut);
+        
+        for expected_ms in [100, 200, 400, 800] {
+            assert_eq!(backoff.on_error(&timed_out), RecvErrorAction::Retry(Duration::from_millis(expected_ms)));
+        }
+        assert_eq!(backoff.on_error(&timed_out), RecvErrorAction::Rebind(Duration::from_millis(1600)));
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        let mut backoff = ListenerBackoff::new();
+        backoff.delay = Duration::from_millis(4000);
+        
+        backoff.on_error(&io_error(std::io::ErrorKind::Interrupted));
+        assert_eq!(backoff.delay, Duration::from_millis(LISTENER_BACKOFF_MAX_MS));
+        backoff.on_error(&io_error(std::io::ErrorKind::Interrupted));
+        assert_eq!(backoff.delay, Duration::from_millis(LISTENER_BACKOFF_MAX_MS));
+    }
+
+    #[test]
+    fn non_transient_error_rebinds_immediately() {
+        let mut backoff = ListenerBackoff::new();
+        let action = backoff.on_error(&io_error(std::io::ErrorKind::NotConnected));
+        assert_eq!(action, RecvErrorAction::Rebind(Duration::from_millis(LISTENER_BACKOFF_MIN_MS)));
+    }
+
+    #[test]
+    fn backoff_resets_after_a_successful_receive() {
+        let mut backoff = ListenerBackoff::new();
+        let timed_out = io_error(std::io::ErrorKind::TimedOut);
+        backoff.on_error(&timed_out);
+        backoff.on_error(&timed_out);
+        
+        backoff.reset();
+        assert_eq!(backoff.consecutive_errors, 0);
+        assert_eq!(backoff.on_error(&timed_out), RecvErrorAction::Retry(Duration::from_millis(LISTENER_BACKOFF_MIN_MS)));
+    }
+
+    #[test]
+    fn linear_response_curve_is_identity() {
+        for value in [-1.0, -0.25, 0.0, 0.25, 1.0] {
+            assert_eq!(ResponseCurve::Linear.apply(value), value);
+        }
+    }
+
+    #[test]
+    fn ease_in_response_curve_squares_the_magnitude() {
+        let curve = ResponseCurve::EaseIn;
+        assert_close(curve.apply(0.5), 0.25);
+        assert_close(curve.apply(-0.5), -0.25);
+        assert_close(curve.apply(1.0), 1.0);
+        assert_close(curve.apply(0.0), 0.0);
+    }
+
+    #[test]
+    fn ease_out_response_curve_rises_early() {
+        let curve = ResponseCurve::EaseOut;
+        assert_close(curve.apply(0.5), 0.75);
+        assert_close(curve.apply(-0.5), -0.75);
+        assert_close(curve.apply(1.0), 1.0);
+        assert_close(curve.apply(0.0), 0.0);
+    }
+
+    #[test]
+    fn piecewise_response_curve_interpolates_and_clamps() {
+        let curve = ResponseCurve::Piecewise { points: vec![(0.0, 0.0), (0.5, 0.8), (1.0, 1.0)] };
+        assert!(curve.validate().is_ok());
+        assert_close(curve.apply(0.25), 0.4);
+        assert_close(curve.apply(0.75), 0.9);
+        assert_close(curve.apply(-1.0), 0.0);
+        assert_close(curve.apply(2.0), 1.0);
+    }
+
+    #[test]
+    fn invalid_piecewise_curves_are_rejected() {
+        let too_short = ResponseCurve::Piecewise { points: vec![(0.0, 0.0)] };
+        let unordered = ResponseCurve::Piecewise { points: vec![(0.5, 0.0), (0.5, 1.0)] };
+        assert!(too_short.validate().is_err());
+        assert!(unordered.validate().is_err());
+        assert!(test_state().set_parameter_curve("Hat", Some(unordered)).is_err());
+    }
+
+    #[test]
+    fn response_curve_shapes_stored_values() {
+        let state = test_state();
+        state.set_parameter_curve("Hat", Some(ResponseCurve::EaseIn)).unwrap();
+        
+        let stored = state.store_parameter(Parameter::new("Hat", ParameterType::Float, 0.5));
+        assert_eq!(stored.value, 0.5);
+        assert_close(stored.shaped_value.unwrap(), 0.25);
+    }
+
+    #[test]
+    fn watch_fires_once_per_crossing_and_rearms() {
+        let state = test_state();
+        state.add_parameter_watch("Hat", WatchCondition::GreaterThan { threshold: 0.5 }, false).unwrap();
+        let fired = |value| state.fire_watches(&Parameter::new("Hat", ParameterType::Float, value)).0.len();
+        
+        assert_eq!(fired(0.2), 0);
+        assert_eq!(fired(0.8), 1);
+        assert_eq!(fired(0.9), 0);
+        assert_eq!(fired(0.3), 0);
+        assert_eq!(fired(0.7), 1);
+    }
+
+    #[test]
+    fn watch_already_true_when_added_does_not_fire() {
+        let state = test_state();
+        state.store_parameter(Parameter::new("Hat", ParameterType::Float, 0.9));
+        state.add_parameter_watch("Hat", WatchCondition::GreaterThan { threshold: 0.5 }, false).unwrap();
+        
+        assert!(state.fire_watches(&Parameter::new("Hat", ParameterType::Float, 1.0)).0.is_empty());
+    }
+
+    #[test]
+    fn desktop_notifications_are_rate_limited() {
+        let state = test_state();
+        state.add_parameter_watch("Hat", WatchCondition::GreaterThan { threshold: 0.5 }, true).unwrap();
+        let crossing = || {
+            state.fire_watches(&Parameter::new("Hat", ParameterType::Float, 0.0));
+            state.fire_watches(&Parameter::new("Hat", ParameterType::Float, 1.0))
+        };
+        
+        let (alerts, notifications) = crossing();
+        assert_eq!((alerts.len(), notifications.len()), (1, 1));
+        let (alerts, notifications) = crossing();
+        assert_eq!((alerts.len(), notifications.len()), (1, 0));
+    }
+
+    #[test]
+    fn ordered_queue_sends_every_value_in_order() {
+        let state = test_state();
+        let receiver = capture_sends(&state);
+        state.set_queue_mode("Hat", QueueMode::Ordered).unwrap();
+        
+        state.queue_parameter("Hat", 1.0, ParameterType::Bool);
+        state.queue_parameter("Hat", 0.0, ParameterType::Bool);
+        assert_eq!(queued_names(&state), vec!["Hat", "Hat"]);
+        
+        state.spawn_workers();
+        assert_eq!(recv_message(&receiver).args, vec![OscType::Bool(true)]);
+        assert_eq!(recv_message(&receiver).args, vec![OscType::Bool(false)]);
+    }
+
+    #[test]
+    fn coalescing_queue_keeps_only_the_latest_value() {
+        let state = test_state();
+        state.queue_parameter("Hat", 1.0, ParameterType::Bool);
+        state.queue_parameter("Hat", 0.0, ParameterType::Bool);
+        
+        let queue = state.send_queue.0.lock().unwrap();
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].value, 0.0);
+    }
+
+    #[test]
+    fn sends_originate_from_the_configured_bind_address() {
+        let state = test_state();
+        let receiver = capture_sends(&state);
+        let config = OscConfig {
+            send_bind_address: "127.0.0.1:0".to_string(),
+            ..state.get_config()
+        };
+        state.update_config(config).unwrap();
+        
+        send_parameter("Hat", 1.0, &ParameterType::Float, &state).unwrap();
+        let (_, source) = receiver.recv_from(&mut [0u8; 1024]).unwrap();
+        assert_eq!(source.ip(), std::net::IpAddr::from([127, 0, 0, 1]));
+    }
+
+    #[test]
+    fn invalid_send_bind_address_is_rejected() {
+        let state = test_state();
+        let config = OscConfig {
+            send_bind_address: "not an address".to_string(),
+            ..state.get_config()
+        };
+        
+        assert!(state.update_config(config).is_err());
+        assert_eq!(state.get_config().send_bind_address, DEFAULT_SEND_BIND_ADDRESS);
+    }
+
+    #[test]
+    fn bool_coercion_is_false_at_the_threshold() {
+        let state = test_state();
+        let receiver = capture_sends(&state);
+        
+        send_parameter("Hat", 0.5, &ParameterType::Bool, &state).unwrap();
+        assert_eq!(recv_message(&receiver).args, vec![OscType::Bool(false)]);
+        send_parameter("Hat", 0.51, &ParameterType::Bool, &state).unwrap();
+        assert_eq!(recv_message(&receiver).args, vec![OscType::Bool(true)]);
+    }
+
+    #[test]
+    fn bool_threshold_is_configurable() {
+        let state = test_state();
+        let receiver = capture_sends(&state);
+        let config = OscConfig {
+            bool_threshold: 0.0,
+            ..state.get_config()
+        };
+        state.update_config(config).unwrap();
+        
+        send_parameter("Hat", -0.1, &ParameterType::Bool, &state).unwrap();
+        assert_eq!(recv_message(&receiver).args, vec![OscType::Bool(false)]);
+        send_parameter("Hat", 0.1, &ParameterType::Bool, &state).unwrap();
+        assert_eq!(recv_message(&receiver).args, vec![OscType::Bool(true)]);
+    }
+
+    #[test]
+    fn explicit_bool_sends_skip_coercion() {
+        let state = test_state();
+        let receiver = capture_sends(&state);
+        
+        send_bool_parameter("Hat", true, &state).unwrap();
+        let msg = recv_message(&receiver);
+        assert_eq!(msg.addr, "/avatar/parameters/Hat");
+        assert_eq!(msg.args, vec![OscType::Bool(true)]);
+        send_bool_parameter("Hat", false, &state).unwrap();
+        assert_eq!(recv_message(&receiver).args, vec![OscType::Bool(false)]);
+    }
+
+    #[test]
+    fn writes_to_read_only_parameters_are_rejected() {
+        let state = test_state();
+        let receiver = capture_sends(&state);
+        state.store_parameter(Parameter::new("VelocityX", ParameterType::Float, 0.0));
+        state.set_parameter_writable("VelocityX", false).unwrap();
+        
+        let error = send_parameter("VelocityX", 1.0, &ParameterType::Float, &state).unwrap_err();
+        assert_eq!(error.to_string(), "Parameter is read-only: VelocityX");
+        assert!(send_bool_parameter("VelocityX", true, &state).is_err());
+        let bundle = [("VelocityX".to_string(), 1.0, ParameterType::Float)];
+        assert!(send_parameter_bundle(&bundle, &state).is_err());
+        
+        receiver.set_read_timeout(Some(Duration::from_millis(100))).unwrap();
+        assert!(receiver.recv_from(&mut [0u8; 1024]).is_err());
+        assert_eq!(state.get_parameter("VelocityX").unwrap().value, 0.0);
+    }
+
+    #[test]
+    fn nested_names_round_trip_through_addresses() {
+        let address = parameter_address("group/sub/leaf");
+        assert_eq!(address, "/avatar/parameters/group/sub/leaf");
+        assert_eq!(parameter_name_from_address(&address), Some("group/sub/leaf"));
+        assert_eq!(parameter_name_from_address("/avatar/parameters/"), None);
+        assert_eq!(parameter_name_from_address("/input/Jump"), None);
+    }
+
+    #[test]
+    fn nested_names_survive_receive_and_send() {
+        let state = test_state();
+        let receiver = capture_sends(&state);
+        
+        let stored = state.process_osc_message(OscMessage {
+            addr: "/avatar/parameters/group/sub".to_string(),
+            args: vec![OscType::Float(0.25)],
+        }).unwrap();
+        assert_eq!(stored.name, "group/sub");
+        
+        send_parameter(&stored.name, 0.75, &ParameterType::Float, &state).unwrap();
+        assert_eq!(recv_message(&receiver).addr, "/avatar/parameters/group/sub");
+    }
+
+    fn float_message(name: &str, value: f32) -> OscMessage {
+        OscMessage {
+            addr: parameter_address(name),
+            args: vec![OscType::Float(value)],
+        }
+    }
+
+    #[test]
+    fn accept_patterns_only_let_matching_names_in() {
+        let state = test_state();
+        state.config.lock().unwrap().accept_patterns = vec!["Hat*".to_string(), "Tail".to_string()];
+        
+        assert!(state.process_osc_message(float_message("HatColor", 1.0)).is_some());
+        assert!(state.process_osc_message(float_message("TailWag", 1.0)).is_some());
+        assert!(state.process_osc_message(float_message("VelocityX", 1.0)).is_none());
+        assert!(state.get_parameter("VelocityX").is_none());
+        assert_eq!(state.get_filtered_message_count(), 1);
+    }
+
+    #[test]
+    fn ignore_patterns_win_over_accept_patterns() {
+        let state = test_state();
+        {
+            let mut config = state.config.lock().unwrap();
+            config.accept_patterns = vec!["Hat*".to_string()];
+            config.ignore_patterns = vec!["*Debug".to_string(), "Velocity".to_string()];
+        }
+        
+        assert!(state.process_osc_message(float_message("Hat", 1.0)).is_some());
+        assert!(state.process_osc_message(float_message("HatDebug", 1.0)).is_none());
+        assert!(state.get_parameter("HatDebug").is_none());
+        assert_eq!(state.get_filtered_message_count(), 1);
+    }
+
+    #[test]
+    fn ignore_patterns_alone_drop_matches() {
+        let state = test_state();
+        state.config.lock().unwrap().ignore_patterns = vec!["Velocity".to_string()];
+        
+        assert!(state.process_osc_message(float_message("AngularVelocityY", 1.0)).is_none());
+        assert!(state.process_osc_message(float_message("Hat", 1.0)).is_some());
+    }
+
+    #[test]
+    fn paused_listener_drops_updates_until_resumed() {
+        let state = loopback_state();
+        state.set_listener_paused(true);
+        
+        send_parameter("Hat", 0.25, &ParameterType::Float, &state).unwrap();
+        thread::sleep(Duration::from_millis(300));
+        assert!(state.get_parameter("Hat").is_none());
+        assert!(*state.running.lock().unwrap());
+        
+        state.set_listener_paused(false);
+        send_parameter("Hat", 0.75, &ParameterType::Float, &state).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while state.get_parameter("Hat").is_none() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(state.get_parameter("Hat").unwrap().value, 0.75);
+        state.stop_listener().unwrap();
+    }
+
+    fn parameter_names(state: &OscState) -> Vec<String> {
+        let mut names: Vec<String> = state.get_parameters().into_iter().map(|p| p.name).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn remove_matching_glob_is_anchored() {
+        let state = test_state();
+        for name in ["Old/Hat", "Old/Tail", "Outfit/Old", "Hat"] {
+            state.update_parameter(Parameter::new(name, ParameterType::Float, 0.0));
+        }
+        
+        assert_eq!(state.remove_parameters_matching("Old/*"), Ok(2));
+        assert_eq!(parameter_names(&state), vec!["Hat", "Outfit/Old"]);
+    }
+
+    #[test]
+    fn remove_matching_without_wildcard_is_a_substring_match() {
+        let state = test_state();
+        for name in ["Old/Hat", "Outfit/Old", "Hat"] {
+            state.update_parameter(Parameter::new(name, ParameterType::Float, 0.0));
+        }
+        
+        assert_eq!(state.remove_parameters_matching("Old"), Ok(2));
+        assert_eq!(parameter_names(&state), vec!["Hat"]);
+        assert_eq!(state.remove_parameters_matching("Missing"), Ok(0));
+    }
+
+    #[test]
+    fn remove_matching_rejects_empty_patterns() {
+        let state = test_state();
+        state.update_parameter(Parameter::new("Hat", ParameterType::Float, 0.0));
+        
+        assert!(state.remove_parameters_matching("  ").is_err());
+        assert_eq!(parameter_names(&state), vec!["Hat"]);
+    }
+
+    #[test]
+    fn remove_matching_drops_the_removed_parameters_settings() {
+        let state = test_state();
+        for name in ["Old/Hat", "Hat"] {
+            state.store_parameter(Parameter::new(name, ParameterType::Float, 0.0));
+            state.add_parameter_watch(name, WatchCondition::GreaterThan { threshold: 0.5 }, false).unwrap();
+            state.set_queue_mode(name, QueueMode::Ordered).unwrap();
+            state.set_parameter_curve(name, Some(ResponseCurve::EaseIn)).unwrap();
+            state.update_rates.lock().unwrap().insert(name.to_string(), UpdateRate::new(Instant::now()));
+        }
+        
+        assert_eq!(state.remove_parameters_matching("Old/*"), Ok(1));
+        
+        let watched: Vec<String> = state.get_parameter_watches().into_iter().map(|w| w.parameter_name).collect();
+        assert_eq!(watched, vec!["Hat"]);
+        for map_keys in [
+            state.queue_modes.lock().unwrap().keys().cloned().collect::<Vec<_>>(),
+            state.curves.lock().unwrap().keys().cloned().collect(),
+            state.update_rates.lock().unwrap().keys().cloned().collect(),
+        ] {
+            assert_eq!(map_keys, vec!["Hat"]);
+        }
+    }
+
+    #[test]
+    fn deadband_suppresses_small_float_changes() {
+        let state = test_state();
+        state.config.lock().unwrap().float_deadband = 0.05;
+        state.process_osc_message(float_message("Tail", 0.5)).unwrap();
+        
+        assert!(state.process_osc_message(float_message("Tail", 0.52)).is_none());
+        assert_eq!(state.get_parameter("Tail").unwrap().value, 0.5);
+        let stored = state.process_osc_message(float_message("Tail", 0.6)).unwrap();
+        assert_eq!(stored.value, 0.6);
+    }
+
+    #[test]
+    fn deadband_never_applies_to_ints_and_bools() {
+        let state = test_state();
+        state.config.lock().unwrap().float_deadband = 5.0;
+        let int_message = |value| OscMessage {
+            addr: parameter_address("Outfit"),
+            args: vec![OscType::Int(value)],
+        };
+        state.process_osc_message(int_message(1)).unwrap();
+        
+        assert_eq!(state.process_osc_message(int_message(2)).unwrap().value, 2.0);
+        let bool_message = OscMessage {
+            addr: parameter_address("Hat"),
+            args: vec![OscType::Bool(false)],
+        };
+        assert!(state.process_osc_message(bool_message.clone()).is_some());
+        assert!(state.process_osc_message(bool_message).is_some());
+    }
+
+    #[test]
+    fn negative_deadband_is_rejected() {
+        let state = test_state();
+        let config = OscConfig {
+            float_deadband: -0.1,
+            ..state.get_config()
+        };
+        assert!(state.update_config(config).is_err());
+    }
+
+    #[test]
+    fn unbindable_port_rolls_the_config_back() {
+        let state = loopback_state();
+        let previous = state.get_config();
+        let bound = state.get_bound_address().unwrap();
+        let occupied = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let config = OscConfig {
+            listen_port: occupied.local_addr().unwrap().port(),
+            target_port: 9000,
+            ..state.get_config()
+        };
+        
+        assert!(state.update_config(config).is_err());
+        let restored = state.get_config();
+        assert_eq!(restored.listen_port, previous.listen_port);
+        assert_eq!(restored.target_port, previous.target_port);
+        assert!(*state.running.lock().unwrap());
+        assert_eq!(state.get_bound_address().unwrap().ip(), bound.ip());
+        state.stop_listener().unwrap();
+    }
+
+    // A loopback bind address on a port that was free a moment ago
+    fn free_bind_address() -> String {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket.local_addr().unwrap().to_string()
+    }
+
+    #[test]
+    fn changing_the_bind_address_rebinds_the_send_socket() {
+        let state = test_state();
+        let receiver = capture_sends(&state);
+        
+        for bind_address in [free_bind_address(), free_bind_address()] {
+            let config = OscConfig {
+                send_bind_address: bind_address.clone(),
+                ..state.get_config()
+            };
+            state.update_config(config).unwrap();
+            
+            send_parameter("Hat", 1.0, &ParameterType::Float, &state).unwrap();
+            let (_, source) = receiver.recv_from(&mut [0u8; 1024]).unwrap();
+            assert_eq!(source.to_string(), bind_address);
+        }
+    }
+
+    #[test]
+    fn color_channels_round_trip() {
+        let color = OscColor { red: 255, green: 128, blue: 0, alpha: 64 };
+        assert_eq!(color_from_values(&color_to_values(&color)), color);
+        
+        let missing = color_from_values(&[1.0, 0.5]);
+        assert_eq!(missing, OscColor { red: 255, green: 128, blue: 0, alpha: 255 });
+    }
+
+    #[test]
+    fn received_color_keeps_every_channel() {
+        let state = test_state();
+        let stored = state.process_osc_message(OscMessage {
+            addr: parameter_address("Tint"),
+            args: vec![OscType::Color(OscColor { red: 255, green: 0, blue: 51, alpha: 255 })],
+        }).unwrap();
+        
+        assert_eq!(stored.parameter_type, ParameterType::Color);
+        assert_eq!(stored.value, 1.0);
+        assert_eq!(stored.values, Some(vec![1.0, 0.0, 0.2, 1.0]));
+    }
+
+    #[test]
+    fn color_sends_as_one_color_argument() {
+        let state = test_state();
+        let receiver = capture_sends(&state);
+        
+        send_parameter_values("Tint", &[1.0, 0.0, 0.2, 1.0], &ParameterType::Color, &state).unwrap();
+        let msg = recv_message(&receiver);
+        assert_eq!(msg.args, vec![OscType::Color(OscColor { red: 255, green: 0, blue: 51, alpha: 255 })]);
+    }
+
+    #[test]
+    fn single_value_color_sends_are_rejected() {
+        let state = test_state();
+        let mut tint = Parameter::new("Tint", ParameterType::Color, 1.0);
+        tint.values = Some(vec![1.0, 0.0, 0.0, 1.0]);
+        state.store_parameter(tint);
+        
+        assert!(send_parameter("Tint", 0.5, &ParameterType::Color, &state).is_err());
+        let bundle = [("Tint".to_string(), 0.5, ParameterType::Color)];
+        assert!(send_parameter_bundle(&bundle, &state).is_err());
+        assert!(state.set_parameter_by_index(0, 0.5).is_err());
+        assert!(queued_names(&state).is_empty());
+    }
+
+    #[test]
+    fn char_arguments_are_stored_as_their_code() {
+        let state = test_state();
+        let stored = state.process_osc_message(OscMessage {
+            addr: parameter_address("Letter"),
+            args: vec![OscType::Char('A')],
+        }).unwrap();
+        
+        assert_eq!(stored.parameter_type, ParameterType::Int);
+        assert_eq!(stored.value, 65.0);
+        assert_eq!(state.get_unsupported_message_count(), 0);
+    }
+
+    #[test]
+    fn unsupported_arguments_are_counted_not_stored() {
+        let state = test_state();
+        let unsupported = [
+            vec![OscType::Nil],
+            vec![OscType::Inf],
+            vec![OscType::String("text".to_string())],
+            vec![OscType::Double(0.5)],
+            vec![OscType::Long(1)],
+            vec![],
+        ];
+        
+        for (index, args) in unsupported.into_iter().enumerate() {
+            let addr = parameter_address(&format!("P{}", index));
+            assert!(state.process_osc_message(OscMessage { addr, args }).is_none());
+        }
+        assert_eq!(state.get_unsupported_message_count(), 6);
+        assert!(state.get_parameters().is_empty());
+    }
+
+    #[test]
+    fn unsupported_types_are_named_in_logs() {
+        assert_eq!(osc_type_name(&OscType::Nil), "Nil");
+        assert_eq!(osc_type_name(&OscType::Inf), "Infinitum");
+        assert_eq!(osc_type_name(&OscType::String("text".to_string())), "String");
+        assert_eq!(osc_type_name(&OscType::Double(0.5)), "Double");
+    }
+
+    #[test]
+    fn watchdog_restarts_a_dead_listener() {
+        let state = loopback_state();
+        let killed = state.listener_thread.lock().unwrap().as_ref().unwrap().stop.clone();
+        state.spawn_workers();
+        
+        // Stopping the thread without going through stop_listener looks like a crash
+        killed.store(true, Ordering::Relaxed);
+        let restarted = || {
+            let listener = state.listener_thread.lock().unwrap();
+            listener.as_ref().map_or(false, |t| !Arc::ptr_eq(&t.stop, &killed) && !t.handle.is_finished())
+        };
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !restarted() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(50));
+        }
+        
+        assert!(restarted());
+        assert!(*state.running.lock().unwrap());
+        state.stop_listener().unwrap();
+    }
+
+    #[test]
+    fn oversized_bundles_are_split_without_losing_values() {
+        let state = test_state();
+        let receiver = capture_sends(&state);
+        state.config.lock().unwrap().max_packet_size = 256;
+        let entries: Vec<(String, f32, ParameterType)> = (0..50)
+            .map(|i| (format!("Param{:02}", i), i as f32 / 100.0, ParameterType::Float))
+            .collect();
+        
+        send_parameter_bundle(&entries, &state).unwrap();
+        let mut received = HashMap::new();
+        let mut bundles = 0;
+        let mut buf = [0u8; 65536];
+        while received.len() < entries.len() {
+            let (size, _) = receiver.recv_from(&mut buf).unwrap();
+            assert!(size <= 256, "bundle of {} bytes", size);
+            let OscPacket::Bundle(bundle) = rosc::decoder::decode_udp(&buf[..size]).unwrap().1 else {
+                panic!("Expected a bundle");
+            };
+            bundles += 1;
+            for packet in bundle.content {
+                let OscPacket::Message(msg) = packet else {
+                    panic!("Expected a message");
+                };
+                received.insert(msg.addr, msg.args);
+            }
+        }
+        
+        assert!(bundles > 1);
+        for (name, value, _) in &entries {
+            assert_eq!(received[&parameter_address(name)], vec![OscType::Float(*value)]);
+        }
+    }
+
+    #[test]
+    fn observed_addresses_cover_every_prefix() {
+        let state = test_state();
+        state.config.lock().unwrap().ignore_patterns = vec!["Velocity".to_string()];
+        let message = |addr: &str| OscPacket::Message(OscMessage {
+            addr: addr.to_string(),
+            args: vec![OscType::Float(0.0)],
+        });
+        
+        state.process_osc_packet(message("/tracking/head"));
+        state.process_osc_packet(message("/avatar/parameters/Hat"));
+        state.process_osc_packet(message("/avatar/parameters/Hat"));
+        state.process_osc_packet(message("/avatar/parameters/VelocityX"));
+        state.process_osc_packet(OscPacket::Bundle(OscBundle {
+            timetag: OscTime { seconds: 0, fractional: 1 },
+            content: vec![message("/input/Jump"), message("/avatar/change")],
+        }));
+        
+        let observed = state.get_observed_addresses();
+        let addresses: Vec<&str> = observed.iter().map(|a| a.address.as_str()).collect();
+        assert_eq!(addresses, vec![
+            "/avatar/change",
+            "/avatar/parameters/Hat",
+            "/avatar/parameters/VelocityX",
+            "/input/Jump",
+            "/tracking/head",
+        ]);
+        assert_eq!(observed[1].count, 2);
+        assert!(observed.iter().all(|a| a.last_seen_ms > 0));
+    }
+
+    #[test]
+    fn observed_addresses_are_bounded() {
+        let state = test_state();
+        for i in 0..MAX_OBSERVED_ADDRESSES + 10 {
+            state.record_observed_address(&format!("/custom/{}", i));
+        }
+        
+        let observed = state.get_observed_addresses();
+        assert_eq!(observed.len(), MAX_OBSERVED_ADDRESSES);
+        let newest = format!("/custom/{}", MAX_OBSERVED_ADDRESSES + 9);
+        assert!(observed.iter().any(|a| a.address == newest));
+    }
+}
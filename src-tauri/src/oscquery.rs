@@ -0,0 +1,90 @@
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use tauri::State;
+
+use crate::osc::{parameter_name_from_address, OscState, Parameter, ParameterType};
+
+// Fetch VRChat's OSCQuery tree and populate the parameter map from it
+pub async fn fetch_parameters(host: &str, port: u16, osc_state: &OscState) -> Result<usize> {
+    let url = format!("http://{}:{}/", host, port);
+    let root: Value = reqwest::get(&url).await?.error_for_status()?.json().await?;
+    
+    let mut found = Vec::new();
+    collect_parameters(&root, &mut found);
+    
+    if found.is_empty() {
+        return Err(anyhow!("No avatar parameters found in OSCQuery tree at {}", url));
+    }
+    
+    let count = found.len();
+    for param in found {
+        osc_state.store_parameter(param);
+    }
+    osc_state.emit_parameters();
+    
+    log::info!("Loaded {} parameters from OSCQuery at {}", count, url);
+    Ok(count)
+}
+
+// Walk the recursive CONTENTS tree, collecting avatar parameter leaves
+fn collect_parameters(node: &Value, found: &mut Vec<Parameter>) {
+    if let Some(param) = parse_parameter_node(node) {
+        found.push(param);
+    }
+    
+    if let Some(contents) = node.get("CONTENTS").and_then(Value::as_object) {
+        for child in contents.values() {
+            collect_parameters(child, found);
+        }
+    }
+}
+
+// Convert a single OSCQuery node into a parameter, if it is a typed avatar parameter
+fn parse_parameter_node(node: &Value) -> Option<Parameter> {
+    let full_path = node.get("FULL_PATH")?.as_str()?;
+    let name = parameter_name_from_address(full_path)?;
+    let type_tag = node.get("TYPE")?.as_str()?;
+    
+    let parameter_type = match type_tag {
+        "f" => ParameterType::Float,
+        "i" => ParameterType::Int,
+        "T" | "F" => ParameterType::Bool,
+        "r" => ParameterType::Color,
+        _ => return None, // Unsupported type
+    };
+    
+    let value = node
+        .get("VALUE")
+        .and_then(Value::as_array)
+        .and_then(|values| values.first())
+        .and_then(|value| match value {
+            Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+            Value::Number(n) => n.as_f64().map(|n| n as f32),
+            _ => None,
+        })
+        .unwrap_or(0.0);
+    
+    let mut param = Parameter::new(name, parameter_type, value);
+    
+    // ACCESS is a bitmask: 1 = readable, 2 = writable
+    if let Some(access) = node.get("ACCESS").and_then(Value::as_u64) {
+        param.writable = access & 2 != 0;
+    }
+    
+    Some(param)
+}
+
+// Tauri commands
+
+#[tauri::command]
+pub async fn fetch_vrchat_parameters(
+    host: Option<String>,
+    port: u16,
+    state: State<'_, OscState>,
+) -> Result<usize, String> {
+    let host = host.unwrap_or_else(|| state.get_config().target_address);
+    
+    fetch_parameters(&host, port, &state)
+        .await
+        .map_err(|e| format!("Failed to fetch VRChat parameters: {}", e))
+}
@@ -0,0 +1,158 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::env;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+// Current schema version written to every persisted JSON file
+pub const SCHEMA_VERSION: u32 = 1;
+
+// Instance id from `--instance <id>` or VRCPARAM_INSTANCE, so several copies of the app can run
+// side by side with separate data. Resolved once; ids are restricted to a safe directory name.
+pub fn instance_id() -> Option<&'static str> {
+    static INSTANCE_ID: OnceLock<Option<String>> = OnceLock::new();
+    INSTANCE_ID
+        .get_or_init(|| {
+            let args: Vec<String> = env::args().collect();
+            let id = args
+                .windows(2)
+                .find(|pair| pair[0] == "--instance")
+                .map(|pair| pair[1].clone())
+                .or_else(|| env::var("VRCPARAM_INSTANCE").ok())?;
+            
+            let valid = !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if !valid {
+                log::warn!("Ignoring invalid instance id: {}", id);
+                return None;
+            }
+            Some(id)
+        })
+        .as_deref()
+}
+
+// Root for persisted data: the app data directory itself, or a per-instance subdirectory.
+// Callers pass `instance_id()`; taking it as an argument keeps the resolution testable
+pub fn instance_data_dir(app_data: PathBuf, instance: Option<&str>) -> PathBuf {
+    match instance {
+        Some(id) => app_data.join("instances").join(id),
+        None => app_data,
+    }
+}
+
+// Pretty JSON by default so files stay hand-editable; compact saves time and space for users
+// with very large files. Reading accepts either
+static COMPACT_JSON: AtomicBool = AtomicBool::new(false);
+
+pub fn set_compact_json(compact: bool) {
+    COMPACT_JSON.store(compact, Ordering::Relaxed);
+}
+
+// Upgrades the `data` of a file from the given version to the next one
+pub type Migration = fn(u32, Value) -> Result<Value, String>;
+
+#[derive(Serialize, Deserialize)]
+struct Envelope<T> {
+    version: u32,
+    data: T,
+}
+
+// Wrap data in a `{"version": N, "data": ...}` envelope
+pub fn to_versioned_json<T: Serialize>(data: &T) -> Result<String, String> {
+    let envelope = Envelope {
+        version: SCHEMA_VERSION,
+        data,
+    };
+    if COMPACT_JSON.load(Ordering::Relaxed) {
+        serde_json::to_string(&envelope).map_err(|e| e.to_string())
+    } else {
+        serde_json::to_string_pretty(&envelope).map_err(|e| e.to_string())
+    }
+}
+
+// Read an enveloped file, upgrading older versions step by step. Files written before
+// the envelope existed are bare data in the version 1 shape.
+pub fn from_versioned_json<T: DeserializeOwned>(json: &str, migrate: Migration) -> Result<T, String> {
+    let value: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    
+    let (mut version, mut data) = match value {
+        Value::Object(mut map) if map.contains_key("version") && map.contains_key("data") => {
+            let version = map
+                .get("version")
+                .and_then(Value::as_u64)
+                .ok_or("Invalid schema version")? as u32;
+            (version, map.remove("data").unwrap_or(Value::Null))
+        }
+        bare => (1, bare),
+    };
+    
+    if version > SCHEMA_VERSION {
+        return Err(format!(
+            "File was written by a newer version of the app (schema {}, supported {})",
+            version, SCHEMA_VERSION
+        ));
+    }
+    
+    while version < SCHEMA_VERSION {
+        data = migrate(version, data)?;
+        version += 1;
+    }
+    
+    serde_json::from_value(data).map_err(|e| e.to_string())
+}
+
+// For files whose shape hasn't changed since version 1
+pub fn no_migrations(version: u32, _data: Value) -> Result<Value, String> {
+    Err(format!("No migration available from schema version {}", version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instance_ids_resolve_to_separate_dirs() {
+        let base = PathBuf::from("data");
+        let first = instance_data_dir(base.clone(), Some("first"));
+        let second = instance_data_dir(base.clone(), Some("second"));
+        
+        assert_eq!(first, base.join("instances").join("first"));
+        assert_eq!(second, base.join("instances").join("second"));
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn no_instance_uses_app_data_dir() {
+        assert_eq!(instance_data_dir(PathBuf::from("data"), None), PathBuf::from("data"));
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Labels {
+        labels: Vec<String>,
+    }
+
+    #[test]
+    fn unversioned_file_loads_as_version_1() {
+        let loaded: Labels = from_versioned_json(r#"{"labels": ["a", "b"]}"#, no_migrations).unwrap();
+        assert_eq!(loaded.labels, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn version_1_envelope_loads() {
+        let loaded: Labels = from_versioned_json(r#"{"version": 1, "data": {"labels": ["a"]}}"#, no_migrations).unwrap();
+        assert_eq!(loaded.labels, vec!["a"]);
+    }
+
+    #[test]
+    fn written_file_round_trips() {
+        let labels = Labels { labels: vec!["a".to_string()] };
+        let json = to_versioned_json(&labels).unwrap();
+        assert_eq!(from_versioned_json::<Labels>(&json, no_migrations).unwrap(), labels);
+    }
+
+    #[test]
+    fn newer_version_is_rejected() {
+        let json = format!(r#"{{"version": {}, "data": {{"labels": []}}}}"#, SCHEMA_VERSION + 1);
+        assert!(from_versioned_json::<Labels>(&json, no_migrations).is_err());
+    }
+}
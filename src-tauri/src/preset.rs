@@ -0,0 +1,442 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::osc::{ensure_scalar, OscState, ParameterType};
+use crate::persist::{from_versioned_json, instance_data_dir, instance_id, no_migrations, to_versioned_json};
+
+// Applying a preset raises the send rate so the entries drain quickly, for at most this long
+const PRESET_BURST_MULTIPLIER: f32 = 4.0;
+const PRESET_BURST_MAX: Duration = Duration::from_secs(10);
+const SEND_QUEUE_POLL: Duration = Duration::from_millis(10);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetEntry {
+    pub name: String,
+    pub parameter_type: ParameterType,
+    pub value: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Preset {
+    pub name: String,
+    pub parameters: Vec<PresetEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetApplyResult {
+    pub applied: usize,
+    // "name: error" for each entry that could not be queued
+    pub failed: Vec<String>,
+    pub cancelled: bool,
+}
+
+// Entry present in both presets with a different value or type
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetEntryChange {
+    pub name: String,
+    pub a: PresetEntry,
+    pub b: PresetEntry,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetDiff {
+    pub only_in_a: Vec<PresetEntry>,
+    pub only_in_b: Vec<PresetEntry>,
+    pub changed: Vec<PresetEntryChange>,
+}
+
+// A preset entry whose value differs from the parameter's current value
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetEntryDelta {
+    pub name: String,
+    pub current: f32,
+    pub target: f32,
+    pub delta: f32,
+}
+
+// What applying a preset would do to the live avatar; each list is sorted by parameter name
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetApplyPreview {
+    pub changes: Vec<PresetEntryDelta>,
+    pub unchanged: Vec<String>,
+    // Entries for parameters that haven't been discovered, so their current value is unknown
+    pub unknown: Vec<PresetEntry>,
+}
+
+// Payload of `preset-apply-started` and `preset-apply-progress`
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetApplyProgress {
+    pub name: String,
+    pub index: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PresetState {
+    presets: Arc<Mutex<HashMap<String, Preset>>>,
+    cancel_apply: Arc<AtomicBool>,
+    app_handle: Arc<Mutex<Option<AppHandle>>>,
+}
+
+impl PresetState {
+    pub fn new() -> Self {
+        Self {
+            presets: Arc::new(Mutex::new(HashMap::new())),
+            cancel_apply: Arc::new(AtomicBool::new(false)),
+            app_handle: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn initialize(&self, app_handle: AppHandle) -> Result<(), String> {
+        *self.app_handle.lock().unwrap() = Some(app_handle);
+        self.load_presets().map_err(|e| format!("Failed to load presets: {}", e))
+    }
+
+    fn get_presets_path(&self) -> Result<PathBuf, String> {
+        if let Some(app_handle) = self.app_handle.lock().unwrap().as_ref() {
+            let app_data = app_handle
+                .path()
+                .app_data_dir()
+                .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+            let dir_path = instance_data_dir(app_data, instance_id()).join("presets");
+            
+            // Ensure the directory exists
+            if !dir_path.exists() {
+                fs::create_dir_all(&dir_path).map_err(|e| format!("Failed to create presets directory: {}", e))?;
+            }
+            
+            Ok(dir_path.join("presets.json"))
+        } else {
+            Err("App handle not initialized".to_string())
+        }
+    }
+
+    pub fn save_presets(&self) -> Result<(), String> {
+        let path = self.get_presets_path()?;
+        let presets = self.presets.lock().unwrap();
+        let json = to_versioned_json(&*presets)
+            .map_err(|e| format!("Failed to serialize presets: {}", e))?;
+        
+        fs::write(path, json).map_err(|e| format!("Failed to write presets to disk: {}", e))?;
+        Ok(())
+    }
+
+    pub fn load_presets(&self) -> Result<(), String> {
+        let path = self.get_presets_path()?;
+        
+        if !path.exists() {
+            return Ok(()); // No presets file yet, that's fine
+        }
+        
+        let json = fs::read_to_string(&path).map_err(|e| format!("Failed to read presets file: {}", e))?;
+        let loaded_presets: HashMap<String, Preset> = from_versioned_json(&json, no_migrations)
+            .map_err(|e| format!("Failed to parse presets JSON: {}", e))?;
+        
+        let mut presets = self.presets.lock().unwrap();
+        *presets = loaded_presets;
+        
+        Ok(())
+    }
+
+    pub fn get_presets(&self) -> Vec<Preset> {
+        let presets = self.presets.lock().unwrap();
+        presets.values().cloned().collect()
+    }
+
+    pub fn get_preset(&self, name: &str) -> Option<Preset> {
+        self.presets.lock().unwrap().get(name).cloned()
+    }
+
+    pub fn save_preset(&self, preset: Preset) -> Result<(), String> {
+        let mut presets = self.presets.lock().unwrap();
+        presets.insert(preset.name.clone(), preset);
+        
+        drop(presets); // Release the lock before saving
+        self.save_presets()
+    }
+
+    pub fn remove_preset(&self, name: &str) -> Result<bool, String> {
+        let removed = self.presets.lock().unwrap().remove(name).is_some();
+        
+        if removed {
+            self.save_presets()?;
+        }
+        
+        Ok(removed)
+    }
+
+    // Queue every entry of a preset and wait for the queue to drain, with the send rate raised
+    // meanwhile. `inter_send_delay_ms` additionally paces the sequence for avatars that drop
+    // parameters arriving in the same tick
+    pub async fn apply_preset(
+        &self,
+        name: &str,
+        inter_send_delay_ms: Option<u64>,
+        osc_state: &OscState,
+    ) -> Result<PresetApplyResult, String> {
+        let preset = self
+            .get_preset(name)
+            .ok_or_else(|| format!("Preset not found: {}", name))?;
+        let delay = Duration::from_millis(inter_send_delay_ms.unwrap_or(0));
+        let total = preset.parameters.len();
+        
+        let mut result = PresetApplyResult {
+            applied: 0,
+            failed: Vec::new(),
+            cancelled: false,
+        };
+        
+        // Ends the burst however this returns
+        let _burst = osc_state.with_burst(PRESET_BURST_MULTIPLIER, PRESET_BURST_MAX)?;
+        
+        self.cancel_apply.store(false, Ordering::Relaxed);
+        self.emit("preset-apply-started", PresetApplyProgress { name: name.to_string(), index: 0, total });
+        
+        for (index, entry) in preset.parameters.iter().enumerate() {
+            if index > 0 && !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            if self.cancel_apply.swap(false, Ordering::Relaxed) {
+                result.cancelled = true;
+                break;
+            }
+            
+            let checked = osc_state
+                .ensure_writable(&entry.name)
+                .and_then(|()| ensure_scalar(&entry.name, &entry.parameter_type));
+            match checked {
+                Ok(()) => {
+                    osc_state.queue_parameter(&entry.name, entry.value, entry.parameter_type.clone());
+                    osc_state.set_or_insert_parameter(&entry.name, entry.value, entry.parameter_type.clone());
+                    result.applied += 1;
+                }
+                Err(e) => result.failed.push(format!("{}: {}", entry.name, e)),
+            }
+            
+            self.emit("preset-apply-progress", PresetApplyProgress { name: name.to_string(), index: index + 1, total });
+        }
+        
+        let deadline = Instant::now() + PRESET_BURST_MAX;
+        while osc_state.pending_send_count() > 0 && Instant::now() < deadline {
+            tokio::time::sleep(SEND_QUEUE_POLL).await;
+        }
+        
+        osc_state.emit_parameters();
+        self.emit("preset-apply-complete", result.clone());
+        Ok(result)
+    }
+
+    // Compare two presets entry by entry; every list is sorted by parameter name
+    pub fn diff_presets(&self, a: &str, b: &str) -> Result<PresetDiff, String> {
+        let preset_a = self.get_preset(a).ok_or_else(|| format!("Preset not found: {}", a))?;
+        let preset_b = self.get_preset(b).ok_or_else(|| format!("Preset not found: {}", b))?;
+        
+        let entries_a: HashMap<&str, &PresetEntry> = preset_a.parameters.iter().map(|e| (e.name.as_str(), e)).collect();
+        let entries_b: HashMap<&str, &PresetEntry> = preset_b.parameters.iter().map(|e| (e.name.as_str(), e)).collect();
+        
+        let mut diff = PresetDiff {
+            only_in_a: Vec::new(),
+            only_in_b: Vec::new(),
+            changed: Vec::new(),
+        };
+        
+        for (name, entry_a) in &entries_a {
+            match entries_b.get(name) {
+                None => diff.only_in_a.push((*entry_a).clone()),
+                Some(entry_b) => {
+                    if entry_a.value != entry_b.value || entry_a.parameter_type != entry_b.parameter_type {
+                        diff.changed.push(PresetEntryChange {
+                            name: name.to_string(),
+                            a: (*entry_a).clone(),
+                            b: (*entry_b).clone(),
+                        });
+                    }
+                }
+            }
+        }
+        diff.only_in_b = entries_b
+            .iter()
+            .filter(|(name, _)| !entries_a.contains_key(*name))
+            .map(|(_, entry)| (*entry).clone())
+            .collect();
+        
+        diff.only_in_a.sort_by(|x, y| x.name.cmp(&y.name));
+        diff.only_in_b.sort_by(|x, y| x.name.cmp(&y.name));
+        diff.changed.sort_by(|x, y| x.name.cmp(&y.name));
+        
+        Ok(diff)
+    }
+
+    // Compare a preset against current parameter values without sending anything
+    pub fn preview_apply(&self, name: &str, osc_state: &OscState) -> Result<PresetApplyPreview, String> {
+        let preset = self
+            .get_preset(name)
+            .ok_or_else(|| format!("Preset not found: {}", name))?;
+        
+        let mut preview = PresetApplyPreview {
+            changes: Vec::new(),
+            unchanged: Vec::new(),
+            unknown: Vec::new(),
+        };
+        
+        for entry in preset.parameters {
+            match osc_state.get_parameter(&entry.name) {
+                None => preview.unknown.push(entry),
+                Some(param) if param.value == entry.value => preview.unchanged.push(entry.name),
+                Some(param) => preview.changes.push(PresetEntryDelta {
+                    name: entry.name,
+                    current: param.value,
+                    target: entry.value,
+                    delta: entry.value - param.value,
+                }),
+            }
+        }
+        
+        preview.changes.sort_by(|x, y| x.name.cmp(&y.name));
+        preview.unchanged.sort();
+        preview.unknown.sort_by(|x, y| x.name.cmp(&y.name));
+        
+        Ok(preview)
+    }
+
+    // Stop an in-progress apply before its next send
+    pub fn cancel_apply(&self) {
+        self.cancel_apply.store(true, Ordering::Relaxed);
+    }
+
+    fn emit<S: Serialize + Clone>(&self, event: &str, payload: S) {
+        if let Some(app_handle) = self.app_handle.lock().unwrap().as_ref() {
+            let _ = app_handle.emit(event, payload);
+        }
+    }
+
+    // Capture the current value of every parameter, optionally limited to a name-prefix group
+    pub fn snapshot_to_preset(&self, name: &str, group: Option<&str>, osc_state: &OscState) -> Result<Preset, String> {
+        let group_prefix = group.map(|g| format!("{}/", g.trim_end_matches('/')));
+        
+        let mut parameters: Vec<PresetEntry> = osc_state
+            .get_parameters()
+            .into_iter()
+            .filter(|p| group_prefix.as_ref().map_or(true, |prefix| p.name.starts_with(prefix)))
+            .map(|p| PresetEntry {
+                name: p.name,
+                parameter_type: p.parameter_type,
+                value: p.value,
+            })
+            .collect();
+        
+        if parameters.is_empty() {
+            return Err("No parameters to snapshot".to_string());
+        }
+        parameters.sort_by(|a, b| a.name.cmp(&b.name));
+        
+        let preset = Preset {
+            name: name.to_string(),
+            parameters,
+        };
+        self.save_preset(preset.clone())?;
+        
+        Ok(preset)
+    }
+}
+
+// Tauri commands
+
+#[tauri::command]
+pub fn get_presets(preset_state: State<PresetState>) -> Vec<Preset> {
+    preset_state.get_presets()
+}
+
+#[tauri::command]
+pub async fn apply_preset(
+    name: String,
+    inter_send_delay_ms: Option<u64>,
+    preset_state: State<'_, PresetState>,
+    osc_state: State<'_, OscState>,
+) -> Result<PresetApplyResult, String> {
+    preset_state.apply_preset(&name, inter_send_delay_ms, &osc_state).await
+}
+
+#[tauri::command]
+pub fn preview_preset_apply(
+    name: &str,
+    preset_state: State<PresetState>,
+    osc_state: State<OscState>,
+) -> Result<PresetApplyPreview, String> {
+    preset_state.preview_apply(name, &osc_state)
+}
+
+#[tauri::command]
+pub fn diff_presets(a: &str, b: &str, preset_state: State<PresetState>) -> Result<PresetDiff, String> {
+    preset_state.diff_presets(a, b)
+}
+
+#[tauri::command]
+pub fn cancel_preset_apply(preset_state: State<PresetState>) {
+    preset_state.cancel_apply()
+}
+
+#[tauri::command]
+pub fn remove_preset(name: &str, preset_state: State<PresetState>) -> Result<bool, String> {
+    preset_state.remove_preset(name)
+}
+
+#[tauri::command]
+pub fn snapshot_to_preset(
+    name: &str,
+    group: Option<&str>,
+    preset_state: State<PresetState>,
+    osc_state: State<OscState>,
+) -> Result<Preset, String> {
+    preset_state.snapshot_to_preset(name, group, &osc_state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::osc::test_support::capture_osc;
+
+    fn entry(name: &str, value: f32) -> PresetEntry {
+        PresetEntry {
+            name: name.to_string(),
+            parameter_type: ParameterType::Float,
+            value,
+        }
+    }
+
+    #[tokio::test]
+    async fn inter_send_delay_paces_the_sequence() {
+        let (osc_state, receiver) = capture_osc();
+        osc_state.spawn_workers();
+        
+        let state = PresetState::new();
+        let preset = Preset {
+            name: "Outfit".to_string(),
+            parameters: vec![entry("A", 0.1), entry("B", 0.2), entry("C", 0.3)],
+        };
+        state.presets.lock().unwrap().insert(preset.name.clone(), preset);
+        
+        // Timestamp packets as they arrive rather than after the apply returns
+        let arrivals = std::thread::spawn(move || {
+            (0..3)
+                .map(|_| {
+                    receiver.recv_from(&mut [0u8; 1024]).unwrap();
+                    Instant::now()
+                })
+                .collect::<Vec<_>>()
+        });
+        let result = state.apply_preset("Outfit", Some(100), &osc_state).await.unwrap();
+        assert_eq!(result.applied, 3);
+        
+        let arrivals = arrivals.join().unwrap();
+        for gap in arrivals.windows(2).map(|pair| pair[1] - pair[0]) {
+            assert!(gap >= Duration::from_millis(90), "sends only {:?} apart", gap);
+        }
+    }
+}
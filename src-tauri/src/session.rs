@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::State;
+
+use crate::osc::{OscConfig, OscState, Parameter};
+use crate::preset::PresetState;
+use crate::speech::{SpeechHistoryEntry, SpeechState};
+
+// Bumped whenever a field is removed or changes meaning; new fields may be added freely
+const SESSION_REPORT_VERSION: u32 = 1;
+
+// Diagnostic snapshot meant for sharing and diffing between users, not for re-applying.
+// Parameters are sorted by name so two reports diff cleanly.
+//
+// {
+//   "report_version": 1,
+//   "exported_at_ms": <unix time in milliseconds>,
+//   "app_version": "<crate version>",
+//   "config": { ...OscConfig },
+//   "parameters": [ { "name", "parameter_type", "value", ... } ],
+//   "speech_history": [ { "timestamp_ms", "text", "language", "matched", "error" } ],
+//   "emit_failures": <frontend events that failed to emit>
+// }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionReport {
+    pub report_version: u32,
+    pub exported_at_ms: u64,
+    pub app_version: String,
+    pub config: OscConfig,
+    pub parameters: Vec<Parameter>,
+    pub speech_history: Vec<SpeechHistoryEntry>,
+    #[serde(default)]
+    pub emit_failures: u64,
+}
+
+// Version and build details for issue triage
+#[derive(Debug, Clone, Serialize)]
+pub struct AppInfo {
+    pub version: String,
+    pub git_commit: Option<String>,
+    pub target: String,
+    pub oscquery: bool,
+}
+
+pub fn app_info() -> AppInfo {
+    AppInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_commit: option_env!("VRCPARAM_GIT_COMMIT").map(str::to_string),
+        target: env!("VRCPARAM_TARGET").to_string(),
+        oscquery: cfg!(feature = "oscquery"),
+    }
+}
+
+pub fn build_report(osc_state: &OscState, speech_state: &SpeechState) -> SessionReport {
+    SessionReport {
+        report_version: SESSION_REPORT_VERSION,
+        exported_at_ms: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        config: osc_state.get_config(),
+        parameters: osc_state.get_parameters(),
+        speech_history: speech_state.get_history(),
+        emit_failures: osc_state.get_emit_failure_count(),
+    }
+}
+
+// Tauri commands
+
+#[tauri::command]
+pub fn get_app_info() -> AppInfo {
+    app_info()
+}
+
+// Re-read all persisted state (config, parameter settings, commands, presets) from disk, e.g.
+// after restoring a backup. Every part is attempted even if an earlier one fails
+#[tauri::command]
+pub fn reload_all_state(
+    osc_state: State<OscState>,
+    speech_state: State<SpeechState>,
+    preset_state: State<PresetState>,
+) -> Result<(), String> {
+    let mut errors = osc_state.reload_from_disk();
+    errors.extend(speech_state.reload_from_disk().err());
+    errors.extend(preset_state.load_presets().err().map(|e| format!("Failed to load presets: {}", e)));
+    
+    osc_state.emit_event("state-reloaded", ());
+    
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+#[tauri::command]
+pub fn export_session(
+    path: &str,
+    osc_state: State<OscState>,
+    speech_state: State<SpeechState>,
+) -> Result<(), String> {
+    let report = build_report(&osc_state, &speech_state);
+    let json = serde_json::to_string_pretty(&report)
+        .map_err(|e| format!("Failed to serialize session report: {}", e))?;
+    
+    fs::write(path, json).map_err(|e| format!("Failed to write session report: {}", e))
+}
@@ -1,32 +1,271 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use regex::Regex;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, State};
-use tauri::api::path::app_data_dir;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager, State};
 
-use crate::osc::{OscState, ParameterType};
+use crate::osc::{parse_parameter_type, OscState, ParameterType, WatchCondition};
+use crate::persist::{from_versioned_json, instance_data_dir, instance_id, no_migrations, to_versioned_json};
+
+// Number of speech inputs kept in the in-memory history
+const SPEECH_HISTORY_LIMIT: usize = 100;
+
+// Quiet period after the last file event before reloading commands.json
+const COMMANDS_RELOAD_DEBOUNCE: Duration = Duration::from_millis(300);
+
+// How a mapping decides whether it fires
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum MatchKind {
+    // Fires when the input contains `command_text`, sending `value`
+    #[default]
+    Phrase,
+    // Fires on any input containing a number, sending that number
+    AnyNumber,
+    // Fires when `command_text` as a regex matches; a numeric first capture group overrides `value`
+    Regex,
+    // `command_text` holds one `{placeholder}` word (e.g. "color {x}"); the captured word is
+    // looked up in the mapping's `lookup` table and fires only for known words
+    Lookup,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegexTestResult {
+    pub valid: bool,
+    pub error: Option<String>,
+    pub matched: bool,
+    pub captured_value: Option<f32>,
+}
+
+// What a mapping sends to when it fires
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum TargetKind {
+    // `parameter_name` is an avatar parameter
+    #[default]
+    AvatarParameter,
+    // `parameter_name` is a VRChat input such as "Jump" or "Vertical"
+    Input,
+    // `parameter_name` is the text posted to the chatbox; `value` is ignored
+    Chatbox,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommandMapping {
     pub command_text: String,
     pub parameter_name: String,
     pub value: f32,
+    #[serde(default)]
+    pub match_kind: MatchKind,
+    // Only fire while another parameter's current value satisfies this guard
+    #[serde(default)]
+    pub condition: Option<CommandCondition>,
+    // Treat the value as a percentage: 75 is sent as 0.75, clamped to [0, 1]
+    #[serde(default)]
+    pub as_percentage: bool,
+    #[serde(default)]
+    pub target: TargetKind,
+    // Word -> value table for `MatchKind::Lookup`
+    #[serde(default)]
+    pub lookup: Option<HashMap<String, f32>>,
+    // Sent as an OSC string instead of `value` when set, for string-typed receivers
+    #[serde(default)]
+    pub string_value: Option<String>,
+    // Set when the target parameter wasn't discovered yet, so its values couldn't be checked
+    #[serde(default)]
+    pub unverified: bool,
+    // Compiled `command_text` for Regex and Lookup mappings, built whenever the mappings change
+    // rather than on every utterance
+    #[serde(skip)]
+    pub matcher: Option<Regex>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandCondition {
+    pub parameter_name: String,
+    pub condition: WatchCondition,
+}
+
+impl CommandCondition {
+    // Unknown parameters never satisfy a guard
+    pub fn is_met(&self, osc_state: &OscState) -> bool {
+        osc_state
+            .get_parameter(&self.parameter_name)
+            .map_or(false, |p| self.condition.matches(p.value))
+    }
+}
+
+// Speech settings persisted alongside the command mappings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeechSettings {
+    // Master switch; while off, speech input is recorded but no mapping fires
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    // Type used when a command targets a parameter that hasn't been discovered yet
+    #[serde(default)]
+    pub default_parameter_type: Option<ParameterType>,
+    // Per-language overrides of the default parameter type
+    #[serde(default)]
+    pub language_parameter_types: HashMap<String, ParameterType>,
+    // Let number-capturing commands understand spelled-out numbers ("twenty five")
+    #[serde(default)]
+    pub spoken_numbers: bool,
+    // Reload commands.json when it is edited outside the app
+    #[serde(default)]
+    pub watch_commands_file: bool,
+    // Match mapping parameter names to discovered parameters ignoring case and surrounding whitespace
+    #[serde(default = "default_true")]
+    pub normalize_parameter_names: bool,
+    // Lowercase speech text and command texts before matching. Turn off for languages where
+    // lowercasing is lossy or case carries meaning
+    #[serde(default = "default_true")]
+    pub case_insensitive_matching: bool,
+    // Per-language overrides of `case_insensitive_matching`
+    #[serde(default)]
+    pub language_case_insensitive: HashMap<String, bool>,
+}
+
+impl Default for SpeechSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            default_parameter_type: None,
+            language_parameter_types: HashMap::new(),
+            spoken_numbers: false,
+            watch_commands_file: false,
+            normalize_parameter_names: true,
+            case_insensitive_matching: true,
+            language_case_insensitive: HashMap::new(),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// Outcome of merging one language's mappings into another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeResult {
+    pub merged: usize,
+    // Command texts that already existed in the target; the target's mapping was kept
+    pub conflicts: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AddCommandOutcome {
+    Added,
+    // Replaced a mapping with the same command text and parameter
+    Updated,
+    Rejected,
+}
+
+// Per-mapping outcome of a batch add
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddCommandResult {
+    pub command_text: String,
+    pub parameter_name: String,
+    pub outcome: AddCommandOutcome,
+    pub error: Option<String>,
+}
+
+// A mapping whose target parameter hasn't been discovered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnknownCommandParameter {
+    pub language: String,
+    pub command_text: String,
+    pub parameter_name: String,
+}
+
+// A mapping that would fire for some text, with the target and value it would send
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeechMatchPreview {
+    pub command_text: String,
+    pub target: TargetKind,
+    // Resolved to the discovered parameter's name for avatar parameters
+    pub parameter_name: String,
+    // None for inputs, chatbox messages and string values
+    pub parameter_type: Option<ParameterType>,
+    pub value: f32,
+    pub string_value: Option<String>,
+    // False when the mapping's condition currently blocks it
+    pub condition_met: bool,
+}
+
+// Outcome of one speech input. `disabled` tells "speech commands are turned off" apart from an
+// input that matched nothing
+#[derive(Debug, Clone, Serialize)]
+pub struct SpeechResult {
+    pub matched: Vec<String>,
+    pub disabled: bool,
+}
+
+// A single processed speech input and what it matched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeechHistoryEntry {
+    pub timestamp_ms: u64,
+    pub text: String,
+    pub language: String,
+    pub matched: Vec<String>,
+    pub error: Option<String>,
+}
+
+// Usage counters for the current session; they are not persisted
+#[derive(Debug, Clone, Default)]
+struct SpeechCounters {
+    utterances: u64,
+    matches: u64,
+    // language -> command text -> times fired
+    fires: HashMap<String, HashMap<String, u64>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandFireCount {
+    pub language: String,
+    pub command_text: String,
+    pub fires: u64,
+}
+
+// Session speech statistics; `commands` includes every current mapping, so ones that never
+// fired show up with zero
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeechStats {
+    pub utterances: u64,
+    pub matches: u64,
+    pub average_matches: f32,
+    pub commands: Vec<CommandFireCount>,
 }
 
 #[derive(Debug, Clone)]
 pub struct SpeechState {
     commands: Arc<Mutex<HashMap<String, Vec<CommandMapping>>>>,
-    app_handle: Option<AppHandle>,
+    settings: Arc<Mutex<SpeechSettings>>,
+    history: Arc<Mutex<VecDeque<SpeechHistoryEntry>>>,
+    counters: Arc<Mutex<SpeechCounters>>,
+    // Contents of our own last write, so the file watcher can ignore it
+    last_written: Arc<Mutex<Option<String>>>,
+    watcher: Arc<Mutex<Option<RecommendedWatcher>>>,
+    app_handle: Arc<Mutex<Option<AppHandle>>>,
+    // Per-instance commands directory, resolved once the app handle is known
+    commands_dir: Arc<Mutex<Option<PathBuf>>>,
 }
 
 impl Default for SpeechState {
     fn default() -> Self {
         Self {
             commands: Arc::new(Mutex::new(HashMap::new())),
-            app_handle: None,
+            settings: Arc::new(Mutex::new(SpeechSettings::default())),
+            history: Arc::new(Mutex::new(VecDeque::new())),
+            counters: Arc::new(Mutex::new(SpeechCounters::default())),
+            last_written: Arc::new(Mutex::new(None)),
+            watcher: Arc::new(Mutex::new(None)),
+            app_handle: Arc::new(Mutex::new(None)),
+            commands_dir: Arc::new(Mutex::new(None)),
         }
     }
 }
@@ -35,41 +274,286 @@ impl SpeechState {
     pub fn new() -> Self {
         Self {
             commands: Arc::new(Mutex::new(HashMap::new())),
-            app_handle: None,
+            settings: Arc::new(Mutex::new(SpeechSettings::default())),
+            history: Arc::new(Mutex::new(VecDeque::new())),
+            counters: Arc::new(Mutex::new(SpeechCounters::default())),
+            last_written: Arc::new(Mutex::new(None)),
+            watcher: Arc::new(Mutex::new(None)),
+            app_handle: Arc::new(Mutex::new(None)),
+            commands_dir: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn initialize(&self, app_handle: AppHandle) -> Result<(), String> {
+        let app_data = app_handle.path().app_data_dir().map_err(|e| format!("Failed to get app data directory: {}", e))?;
+        *self.commands_dir.lock().unwrap() = Some(instance_data_dir(app_data, instance_id()).join("commands"));
+        *self.app_handle.lock().unwrap() = Some(app_handle);
+        self.load_settings().map_err(|e| format!("Failed to load speech settings: {}", e))?;
+        self.load_commands().map_err(|e| format!("Failed to load commands: {}", e))?;
+        
+        if self.settings.lock().unwrap().watch_commands_file {
+            self.start_commands_watcher()?;
+        }
+        
+        Ok(())
+    }
+
+    // Re-read settings and commands after the files were changed outside the app
+    pub fn reload_from_disk(&self) -> Result<(), String> {
+        self.load_settings().map_err(|e| format!("Failed to load speech settings: {}", e))?;
+        self.load_commands().map_err(|e| format!("Failed to load commands: {}", e))?;
+        
+        let watch = self.settings.lock().unwrap().watch_commands_file;
+        if watch {
+            self.start_commands_watcher()?;
+        } else {
+            self.watcher.lock().unwrap().take();
+        }
+        
+        if let Some(app_handle) = self.app_handle.lock().unwrap().as_ref() {
+            let _ = app_handle.emit("commands-reloaded", ());
         }
+        Ok(())
     }
 
-    pub fn initialize(&mut self, app_handle: AppHandle) -> Result<(), String> {
-        self.app_handle = Some(app_handle);
-        self.load_commands().map_err(|e| format!("Failed to load commands: {}", e))
+    fn get_commands_dir(&self) -> Result<PathBuf, String> {
+        let dir_path = self
+            .commands_dir
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or("App data directory not resolved")?;
+        
+        // Ensure the directory exists
+        if !dir_path.exists() {
+            fs::create_dir_all(&dir_path).map_err(|e| format!("Failed to create commands directory: {}", e))?;
+        }
+        
+        Ok(dir_path)
     }
 
     fn get_commands_path(&self) -> Result<PathBuf, String> {
-        if let Some(app_handle) = &self.app_handle {
-            let app_data = app_data_dir(&app_handle.config()).map_err(|e| format!("Failed to get app data directory: {}", e))?;
-            let dir_path = app_data.join("commands");
-            
-            // Ensure the directory exists
-            if !dir_path.exists() {
-                fs::create_dir_all(&dir_path).map_err(|e| format!("Failed to create commands directory: {}", e))?;
+        Ok(self.get_commands_dir()?.join("commands.json"))
+    }
+
+    fn get_settings_path(&self) -> Result<PathBuf, String> {
+        Ok(self.get_commands_dir()?.join("settings.json"))
+    }
+
+    pub fn save_settings(&self) -> Result<(), String> {
+        let path = self.get_settings_path()?;
+        let settings = self.settings.lock().unwrap();
+        let json = to_versioned_json(&*settings)
+            .map_err(|e| format!("Failed to serialize speech settings: {}", e))?;
+        
+        fs::write(path, json).map_err(|e| format!("Failed to write speech settings to disk: {}", e))?;
+        Ok(())
+    }
+
+    pub fn load_settings(&self) -> Result<(), String> {
+        let path = self.get_settings_path()?;
+        
+        if !path.exists() {
+            return Ok(()); // No settings file yet, defaults apply
+        }
+        
+        let json = fs::read_to_string(&path).map_err(|e| format!("Failed to read speech settings file: {}", e))?;
+        let loaded_settings: SpeechSettings = from_versioned_json(&json, no_migrations)
+            .map_err(|e| format!("Failed to parse speech settings JSON: {}", e))?;
+        
+        let mut settings = self.settings.lock().unwrap();
+        *settings = loaded_settings;
+        
+        Ok(())
+    }
+
+    // Set the fallback parameter type, globally when no language is given
+    pub fn set_default_parameter_type(&self, language: Option<&str>, param_type: Option<ParameterType>) -> Result<(), String> {
+        let mut settings = self.settings.lock().unwrap();
+        
+        match (language, param_type) {
+            (Some(language), Some(param_type)) => {
+                settings.language_parameter_types.insert(language.to_string(), param_type);
             }
-            
-            Ok(dir_path.join("commands.json"))
-        } else {
-            Err("App handle not initialized".to_string())
+            (Some(language), None) => {
+                settings.language_parameter_types.remove(language);
+            }
+            (None, param_type) => {
+                settings.default_parameter_type = param_type;
+            }
+        }
+        
+        drop(settings); // Release the lock before saving
+        self.save_settings()
+    }
+
+    // Set case-insensitive matching, globally when no language is given. Clearing a language
+    // override (None) makes it follow the global setting again
+    pub fn set_case_insensitive_matching(&self, language: Option<&str>, enabled: Option<bool>) -> Result<(), String> {
+        let mut settings = self.settings.lock().unwrap();
+        
+        match (language, enabled) {
+            (Some(language), Some(enabled)) => {
+                settings.language_case_insensitive.insert(language.to_string(), enabled);
+            }
+            (Some(language), None) => {
+                settings.language_case_insensitive.remove(language);
+            }
+            (None, enabled) => {
+                settings.case_insensitive_matching = enabled.unwrap_or(true);
+            }
+        }
+        
+        drop(settings); // Release the lock before recompiling and saving
+        self.compile_matchers();
+        self.save_settings()
+    }
+
+    pub fn is_case_insensitive(&self, language: &str) -> bool {
+        let settings = self.settings.lock().unwrap();
+        settings
+            .language_case_insensitive
+            .get(language)
+            .copied()
+            .unwrap_or(settings.case_insensitive_matching)
+    }
+
+    pub fn set_normalize_parameter_names(&self, enabled: bool) -> Result<(), String> {
+        self.settings.lock().unwrap().normalize_parameter_names = enabled;
+        self.save_settings()
+    }
+
+    pub fn set_speech_enabled(&self, enabled: bool) -> Result<(), String> {
+        self.settings.lock().unwrap().enabled = enabled;
+        self.save_settings()?;
+        
+        if let Some(app_handle) = self.app_handle.lock().unwrap().as_ref() {
+            let _ = app_handle.emit("speech-enabled-changed", enabled);
         }
+        Ok(())
+    }
+
+    pub fn is_speech_enabled(&self) -> bool {
+        self.settings.lock().unwrap().enabled
+    }
+
+    pub fn set_spoken_numbers(&self, enabled: bool) -> Result<(), String> {
+        self.settings.lock().unwrap().spoken_numbers = enabled;
+        self.save_settings()
+    }
+
+    // Resolve the fallback type for a language: language override, then global, then Float
+    pub fn get_default_parameter_type(&self, language: &str) -> ParameterType {
+        let settings = self.settings.lock().unwrap();
+        settings
+            .language_parameter_types
+            .get(language)
+            .or(settings.default_parameter_type.as_ref())
+            .cloned()
+            .unwrap_or(ParameterType::Float)
     }
 
     pub fn save_commands(&self) -> Result<(), String> {
         let path = self.get_commands_path()?;
         let commands = self.commands.lock().unwrap();
-        let json = serde_json::to_string_pretty(&*commands)
+        let json = to_versioned_json(&*commands)
             .map_err(|e| format!("Failed to serialize commands: {}", e))?;
         
+        *self.last_written.lock().unwrap() = Some(json.clone());
         fs::write(path, json).map_err(|e| format!("Failed to write commands to disk: {}", e))?;
         Ok(())
     }
 
+    // Stop watching the commands file before exit; commands and settings are saved on every change
+    pub fn shutdown(&self) {
+        self.watcher.lock().unwrap().take();
+    }
+
+    // Enable or disable reloading commands.json on external edits
+    pub fn set_commands_watch(&self, enabled: bool) -> Result<(), String> {
+        self.settings.lock().unwrap().watch_commands_file = enabled;
+        self.save_settings()?;
+        
+        if enabled {
+            self.start_commands_watcher()
+        } else {
+            // Dropping the watcher closes its channel, which ends the reload thread
+            self.watcher.lock().unwrap().take();
+            Ok(())
+        }
+    }
+
+    fn start_commands_watcher(&self) -> Result<(), String> {
+        let mut watcher_ref = self.watcher.lock().unwrap();
+        if watcher_ref.is_some() {
+            return Ok(()); // Already watching
+        }
+        
+        let dir_path = self.get_commands_dir()?;
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx)
+            .map_err(|e| format!("Failed to create commands watcher: {}", e))?;
+        watcher
+            .watch(&dir_path, RecursiveMode::NonRecursive)
+            .map_err(|e| format!("Failed to watch commands directory: {}", e))?;
+        
+        let state = self.clone();
+        thread::spawn(move || state.run_commands_watcher(rx));
+        
+        *watcher_ref = Some(watcher);
+        log::info!("Watching {} for command changes", dir_path.display());
+        Ok(())
+    }
+
+    fn run_commands_watcher(&self, rx: Receiver<notify::Result<notify::Event>>) {
+        while let Ok(event) = rx.recv() {
+            let touches_commands = event.map_or(false, |event| {
+                (event.kind.is_modify() || event.kind.is_create())
+                    && event.paths.iter().any(|p| p.file_name().map_or(false, |n| n == "commands.json"))
+            });
+            if !touches_commands {
+                continue;
+            }
+            
+            // Wait for writes to settle so we don't read a partially written file
+            loop {
+                match rx.recv_timeout(COMMANDS_RELOAD_DEBOUNCE) {
+                    Ok(_) => continue,
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+            
+            self.reload_changed_commands();
+        }
+    }
+
+    fn reload_changed_commands(&self) {
+        let path = match self.get_commands_path() {
+            Ok(path) => path,
+            Err(e) => {
+                log::error!("Failed to resolve commands path: {}", e);
+                return;
+            }
+        };
+        
+        // Skip reloading our own writes
+        let contents = fs::read_to_string(&path).ok();
+        if contents.is_some() && contents == *self.last_written.lock().unwrap() {
+            return;
+        }
+        
+        match self.load_commands() {
+            Ok(()) => {
+                log::info!("Reloaded commands after external change");
+                if let Some(app_handle) = self.app_handle.lock().unwrap().as_ref() {
+                    let _ = app_handle.emit("commands-reloaded", ());
+                }
+            }
+            Err(e) => log::error!("Failed to reload commands: {}", e),
+        }
+    }
+
     pub fn load_commands(&self) -> Result<(), String> {
         let path = match self.get_commands_path() {
             Ok(p) => p,
@@ -81,31 +565,32 @@ impl SpeechState {
         }
         
         let json = fs::read_to_string(&path).map_err(|e| format!("Failed to read commands file: {}", e))?;
-        let loaded_commands: HashMap<String, Vec<CommandMapping>> = serde_json::from_str(&json)
+        let loaded_commands: HashMap<String, Vec<CommandMapping>> = from_versioned_json(&json, migrate_commands)
             .map_err(|e| format!("Failed to parse commands JSON: {}", e))?;
         
-        let mut commands = self.commands.lock().unwrap();
-        *commands = loaded_commands;
+        *self.commands.lock().unwrap() = loaded_commands;
+        self.compile_matchers();
         
         Ok(())
     }
 
-    pub fn add_command_mapping(&self, language: &str, mapping: CommandMapping) -> Result<(), String> {
+    // Rebuild every mapping's matcher, after loading or when a language's case setting changes
+    fn compile_matchers(&self) {
         let mut commands = self.commands.lock().unwrap();
-        let language_commands = commands.entry(language.to_string()).or_insert_with(Vec::new);
-        
-        // Check if a command with the same text and parameter already exists
-        let existing_idx = language_commands.iter().position(|cmd| {
-            cmd.command_text == mapping.command_text && cmd.parameter_name == mapping.parameter_name
-        });
-        
-        if let Some(idx) = existing_idx {
-            // Update the existing command
-            language_commands[idx] = mapping;
-        } else {
-            // Add new command
-            language_commands.push(mapping);
+        for (language, mappings) in commands.iter_mut() {
+            let fold_case = self.is_case_insensitive(language);
+            for mapping in mappings.iter_mut() {
+                compile_matcher(mapping, fold_case);
+            }
         }
+    }
+
+    pub fn add_command_mapping(&self, language: &str, mut mapping: CommandMapping) -> Result<(), String> {
+        validate_mapping(&mapping)?;
+        compile_matcher(&mut mapping, self.is_case_insensitive(language));
+        
+        let mut commands = self.commands.lock().unwrap();
+        insert_mapping(commands.entry(language.to_string()).or_insert_with(Vec::new), mapping);
         
         // Save commands to disk
         drop(commands); // Release the lock before saving
@@ -114,6 +599,55 @@ impl SpeechState {
         Ok(())
     }
 
+    // Add or update many mappings with a single save. Invalid mappings are reported and skipped
+    // without affecting the rest
+    pub fn add_command_mappings(
+        &self,
+        language: &str,
+        mappings: Vec<CommandMapping>,
+        osc_state: &OscState,
+    ) -> Result<Vec<AddCommandResult>, String> {
+        let mut results = Vec::with_capacity(mappings.len());
+        let fold_case = self.is_case_insensitive(language);
+        
+        let mut commands = self.commands.lock().unwrap();
+        let language_commands = commands.entry(language.to_string()).or_insert_with(Vec::new);
+        for mut mapping in mappings {
+            let (command_text, parameter_name) = (mapping.command_text.clone(), mapping.parameter_name.clone());
+            mapping.unverified = false;
+            let checked = validate_mapping(&mapping).and_then(|()| self.verify_mapping_values(&mut mapping, osc_state));
+            compile_matcher(&mut mapping, fold_case);
+            let (outcome, error) = match checked {
+                Ok(()) if insert_mapping(language_commands, mapping) => (AddCommandOutcome::Updated, None),
+                Ok(()) => (AddCommandOutcome::Added, None),
+                Err(e) => (AddCommandOutcome::Rejected, Some(e)),
+            };
+            results.push(AddCommandResult { command_text, parameter_name, outcome, error });
+        }
+        drop(commands); // Release the lock before saving
+        
+        if results.iter().any(|r| r.outcome != AddCommandOutcome::Rejected) {
+            self.save_commands()?;
+        }
+        Ok(results)
+    }
+
+    // Check a mapping's fixed values against its target parameter's type when that parameter is
+    // known, otherwise flag it as unverified. Only numeric avatar parameter sends have a type
+    pub fn verify_mapping_values(&self, mapping: &mut CommandMapping, osc_state: &OscState) -> Result<(), String> {
+        if mapping.target != TargetKind::AvatarParameter || mapping.string_value.is_some() {
+            return Ok(());
+        }
+        
+        let normalize_names = self.settings.lock().unwrap().normalize_parameter_names;
+        match osc_state.find_parameter(&mapping.parameter_name, normalize_names) {
+            Some(param) => validate_mapping_values(mapping, &param.parameter_type)
+                .map_err(|e| format!("Invalid value for {}: {}", param.name, e))?,
+            None => mapping.unverified = true,
+        }
+        Ok(())
+    }
+
     pub fn remove_command_mapping(&self, language: &str, command_text: &str, parameter_name: &str) -> Result<bool, String> {
         let mut commands = self.commands.lock().unwrap();
         let mut removed = false;
@@ -133,66 +667,640 @@ impl SpeechState {
         Ok(removed)
     }
 
+    // Clone one mapping into another language as a starting point for translation
+    pub fn copy_command_mapping(
+        &self,
+        from_language: &str,
+        to_language: &str,
+        command_text: &str,
+        parameter_name: &str,
+    ) -> Result<(), String> {
+        let mapping = self
+            .get_commands(from_language)
+            .into_iter()
+            .find(|m| m.command_text == command_text && m.parameter_name == parameter_name)
+            .ok_or_else(|| format!("Command not found in {}: {}", from_language, command_text))?;
+        
+        self.add_command_mapping(to_language, mapping)
+    }
+
+    // Move all mappings from one language into another, keeping the target's on conflict
+    pub fn merge_languages(&self, from: &str, to: &str) -> Result<MergeResult, String> {
+        if from == to {
+            return Err("Cannot merge a language into itself".to_string());
+        }
+        
+        let mut commands = self.commands.lock().unwrap();
+        let source = commands
+            .remove(from)
+            .ok_or_else(|| format!("No commands found for language: {}", from))?;
+        let target = commands.entry(to.to_string()).or_insert_with(Vec::new);
+        
+        let mut result = MergeResult {
+            merged: 0,
+            conflicts: Vec::new(),
+        };
+        
+        for mapping in source {
+            let exists = target.iter().any(|cmd| {
+                cmd.command_text == mapping.command_text && cmd.parameter_name == mapping.parameter_name
+            });
+            
+            if exists {
+                result.conflicts.push(mapping.command_text);
+            } else {
+                target.push(mapping);
+                result.merged += 1;
+            }
+        }
+        
+        drop(commands); // Release the lock before recompiling and saving
+        // Lookup matchers follow the target language's case setting
+        self.compile_matchers();
+        self.save_commands()?;
+        
+        Ok(result)
+    }
+
     pub fn get_commands(&self, language: &str) -> Vec<CommandMapping> {
         let commands = self.commands.lock().unwrap();
         commands.get(language).cloned().unwrap_or_default()
     }
 
-    pub fn process_speech_input(&self, text: &str, language: &str, osc_state: &OscState) -> Result<Vec<String>, String> {
+    // Mappings whose parameters aren't currently known, optionally limited to one language
+    pub fn validate_commands(&self, language: Option<&str>, osc_state: &OscState) -> Vec<UnknownCommandParameter> {
+        let normalize_names = self.settings.lock().unwrap().normalize_parameter_names;
+        let commands = self.get_all_commands();
+        
+        let mut unknown: Vec<UnknownCommandParameter> = commands
+            .iter()
+            .filter(|(lang, _)| language.map_or(true, |l| l == lang.as_str()))
+            .flat_map(|(lang, mappings)| mappings.iter().map(move |m| (lang, m)))
+            .filter(|(_, m)| m.target == TargetKind::AvatarParameter)
+            .filter(|(_, m)| osc_state.find_parameter(&m.parameter_name, normalize_names).is_none())
+            .map(|(lang, m)| UnknownCommandParameter {
+                language: lang.clone(),
+                command_text: m.command_text.clone(),
+                parameter_name: m.parameter_name.clone(),
+            })
+            .collect();
+        
+        unknown.sort_by(|a, b| (&a.language, &a.command_text).cmp(&(&b.language, &b.command_text)));
+        unknown
+    }
+
+    pub fn get_all_commands(&self) -> HashMap<String, Vec<CommandMapping>> {
+        self.commands.lock().unwrap().clone()
+    }
+
+    pub fn count_commands(&self) -> HashMap<String, usize> {
+        let commands = self.commands.lock().unwrap();
+        commands.iter().map(|(language, mappings)| (language.clone(), mappings.len())).collect()
+    }
+
+    pub fn process_speech_input(&self, text: &str, language: &str, osc_state: &OscState) -> Result<SpeechResult, String> {
+        // While disabled nothing fires; the history entry is tagged so it's clear why
+        let enabled = self.is_speech_enabled();
+        let result = if enabled {
+            self.counters.lock().unwrap().utterances += 1;
+            self.execute_speech_input(text, language, osc_state)
+        } else {
+            Ok(Vec::new())
+        };
+        
+        let (matched, error) = match &result {
+            Ok(_) if !enabled => (Vec::new(), Some("disabled".to_string())),
+            Ok(matched) => (matched.clone(), None),
+            Err(e) => (Vec::new(), Some(e.clone())),
+        };
+        self.record_history(SpeechHistoryEntry {
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            text: text.to_string(),
+            language: language.to_string(),
+            matched,
+            error,
+        });
+        
+        result.map(|matched| SpeechResult { matched, disabled: !enabled })
+    }
+
+    // Append to the bounded history, dropping the oldest entries
+    fn record_history(&self, entry: SpeechHistoryEntry) {
+        let mut history = self.history.lock().unwrap();
+        while history.len() >= SPEECH_HISTORY_LIMIT {
+            history.pop_front();
+        }
+        history.push_back(entry);
+    }
+
+    // Speech history, oldest first
+    pub fn get_history(&self) -> Vec<SpeechHistoryEntry> {
+        self.history.lock().unwrap().iter().cloned().collect()
+    }
+
+    pub fn clear_history(&self) {
+        self.history.lock().unwrap().clear();
+    }
+
+    fn record_fire(&self, language: &str, command_text: &str) {
+        let mut counters = self.counters.lock().unwrap();
+        counters.matches += 1;
+        *counters
+            .fires
+            .entry(language.to_string())
+            .or_default()
+            .entry(command_text.to_string())
+            .or_default() += 1;
+    }
+
+    // Commands sorted by fire count, most used first
+    pub fn get_stats(&self) -> SpeechStats {
+        let counters = self.counters.lock().unwrap().clone();
+        
+        let mut commands: Vec<CommandFireCount> = self
+            .get_all_commands()
+            .into_iter()
+            .flat_map(|(language, mappings)| {
+                let fires = counters.fires.get(&language).cloned().unwrap_or_default();
+                mappings
+                    .into_iter()
+                    .map(|m| m.command_text)
+                    .collect::<HashSet<_>>()
+                    .into_iter()
+                    .map(move |command_text| CommandFireCount {
+                        fires: fires.get(&command_text).copied().unwrap_or(0),
+                        language: language.clone(),
+                        command_text,
+                    })
+            })
+            .collect();
+        commands.sort_by(|a, b| {
+            b.fires
+                .cmp(&a.fires)
+                .then_with(|| (&a.language, &a.command_text).cmp(&(&b.language, &b.command_text)))
+        });
+        
+        SpeechStats {
+            utterances: counters.utterances,
+            matches: counters.matches,
+            average_matches: if counters.utterances > 0 {
+                counters.matches as f32 / counters.utterances as f32
+            } else {
+                0.0
+            },
+            commands,
+        }
+    }
+
+    // Dry run of speech matching: report what each matching mapping would send without
+    // sending anything or recording history
+    pub fn preview_matches(&self, text: &str, language: &str, osc_state: &OscState) -> Vec<SpeechMatchPreview> {
+        let default_type = self.get_default_parameter_type(language);
+        let (spoken_numbers, normalize_names) = {
+            let settings = self.settings.lock().unwrap();
+            (settings.spoken_numbers, settings.normalize_parameter_names)
+        };
+        let fold_case = self.is_case_insensitive(language);
+        let text_lower = text.to_lowercase();
+        
+        self.get_commands(language)
+            .into_iter()
+            .filter_map(|mapping| {
+                let value = match_value(&mapping, text, &text_lower, fold_case, spoken_numbers)?;
+                let (parameter_name, parameter_type) = match mapping.target {
+                    TargetKind::AvatarParameter if mapping.string_value.is_none() => {
+                        let (name, param_type, _) = resolve_parameter(&mapping, osc_state, normalize_names, &default_type);
+                        (name, Some(param_type))
+                    }
+                    _ => (mapping.parameter_name.clone(), None),
+                };
+                
+                Some(SpeechMatchPreview {
+                    condition_met: mapping.condition.as_ref().map_or(true, |c| c.is_met(osc_state)),
+                    command_text: mapping.command_text,
+                    target: mapping.target,
+                    parameter_name,
+                    parameter_type,
+                    value,
+                    string_value: mapping.string_value,
+                })
+            })
+            .collect()
+    }
+
+    fn execute_speech_input(&self, text: &str, language: &str, osc_state: &OscState) -> Result<Vec<String>, String> {
         let mappings = self.get_commands(language);
+        let default_type = self.get_default_parameter_type(language);
+        let (spoken_numbers, normalize_names) = {
+            let settings = self.settings.lock().unwrap();
+            (settings.spoken_numbers, settings.normalize_parameter_names)
+        };
+        let fold_case = self.is_case_insensitive(language);
         let mut processed_commands = Vec::new();
 
         let text_lower = text.to_lowercase();
         
         for mapping in mappings {
-            if text_lower.contains(&mapping.command_text.to_lowercase()) {
-                // Find the parameter in our known parameters
-                let parameters = osc_state.get_parameters();
-                let param_type = parameters
-                    .iter()
-                    .find(|p| p.name == mapping.parameter_name)
-                    .map(|p| &p.parameter_type)
-                    .unwrap_or(&ParameterType::Float); // Default to float if not found
-                
-                // Send the parameter to VRChat via OSC
-                match crate::osc::send_parameter(&mapping.parameter_name, mapping.value, param_type) {
-                    Ok(_) => {
-                        processed_commands.push(format!("{} -> {}: {}", 
-                            mapping.command_text, 
-                            mapping.parameter_name, 
-                            mapping.value
-                        ));
-                    }
-                    Err(e) => {
-                        return Err(format!("Failed to send parameter: {}", e));
-                    }
+            let value = match match_value(&mapping, text, &text_lower, fold_case, spoken_numbers) {
+                Some(value) => value,
+                None => continue,
+            };
+            
+            if let Some(condition) = &mapping.condition {
+                if !condition.is_met(osc_state) {
+                    processed_commands.push(format!("{} -> {}: skipped (condition)",
+                        mapping.command_text,
+                        mapping.parameter_name
+                    ));
+                    continue;
                 }
             }
-        }
-        
-        Ok(processed_commands)
-    }
-}
-
-// Tauri commands
+            
+            // Inputs and chatbox messages bypass parameter lookup entirely
+            match mapping.target {
+                TargetKind::AvatarParameter => {}
+                TargetKind::Input => {
+                    crate::osc::send_input(&mapping.parameter_name, value, osc_state)
+                        .map_err(|e| format!("Failed to send input: {}", e))?;
+                    self.record_fire(language, &mapping.command_text);
+                    processed_commands.push(format!("{} -> input {}: {}",
+                        mapping.command_text,
+                        mapping.parameter_name,
+                        value
+                    ));
+                    continue;
+                }
+                TargetKind::Chatbox => {
+                    crate::osc::send_chatbox(&mapping.parameter_name, osc_state)
+                        .map_err(|e| format!("Failed to send chatbox message: {}", e))?;
+                    self.record_fire(language, &mapping.command_text);
+                    processed_commands.push(format!("{} -> chatbox: {}",
+                        mapping.command_text,
+                        mapping.parameter_name
+                    ));
+                    continue;
+                }
+            }
+            
+            if let Some(string_value) = &mapping.string_value {
+                let parameter_name = osc_state
+                    .find_parameter(&mapping.parameter_name, normalize_names)
+                    .map_or_else(|| mapping.parameter_name.clone(), |p| p.name);
+                crate::osc::send_string_parameter(&parameter_name, string_value, osc_state)
+                    .map_err(|e| format!("Failed to send parameter: {}", e))?;
+                self.record_fire(language, &mapping.command_text);
+                processed_commands.push(format!("{} -> {}: \"{}\"",
+                    mapping.command_text,
+                    parameter_name,
+                    string_value
+                ));
+                continue;
+            }
+            
+            let (parameter_name, param_type, known) = resolve_parameter(&mapping, osc_state, normalize_names, &default_type);
+            if !known {
+                log::warn!(
+                    "Command '{}' targets unknown parameter {}, sending as {}",
+                    mapping.command_text,
+                    mapping.parameter_name,
+                    default_type.as_str()
+                );
+            }
+            
+            // Send the parameter to VRChat via OSC
+            match crate::osc::send_parameter(&parameter_name, value, &param_type, osc_state) {
+                Ok(_) => {
+                    self.record_fire(language, &mapping.command_text);
+                    processed_commands.push(format!("{} -> {}: {}", 
+                        mapping.command_text, 
+                        parameter_name, 
+                        value
+                    ));
+                }
+                Err(e) => {
+                    return Err(format!("Failed to send parameter: {}", e));
+                }
+            }
+        }
+        
+        Ok(processed_commands)
+    }
+}
+
+// Upgrade commands.json data one schema version at a time. There are no shape changes yet;
+// add cases here when `CommandMapping` changes in a way serde defaults can't cover.
+fn migrate_commands(version: u32, data: serde_json::Value) -> Result<serde_json::Value, String> {
+    no_migrations(version, data)
+}
+
+// The value a mapping would send for this input, or None if it doesn't match. Percentages
+// are already scaled. Without `fold_case`, phrases and lookups match the raw text exactly;
+// regexes always see the raw text and numbers are case-free either way
+fn match_value(mapping: &CommandMapping, text: &str, text_lower: &str, fold_case: bool, spoken_numbers: bool) -> Option<f32> {
+    let haystack = if fold_case { text_lower } else { text };
+    let value = match mapping.match_kind {
+        MatchKind::Phrase => {
+            let matched = if fold_case {
+                haystack.contains(&mapping.command_text.to_lowercase())
+            } else {
+                haystack.contains(&mapping.command_text)
+            };
+            if !matched {
+                return None;
+            }
+            mapping.value
+        }
+        MatchKind::AnyNumber => extract_number(text_lower, spoken_numbers)?,
+        MatchKind::Regex => {
+            let captures = mapping.matcher.as_ref()?.captures(text)?;
+            captured_number(&captures).unwrap_or(mapping.value)
+        }
+        MatchKind::Lookup => {
+            let word = mapping.matcher.as_ref()?.captures(haystack)?.get(1)?.as_str().to_string();
+            lookup_value(mapping.lookup.as_ref(), &word, fold_case)?
+        }
+    };
+    
+    Some(if mapping.as_percentage { percentage_to_unit(value) } else { value })
+}
+
+// Use the discovered parameter's real name and type, then the imported avatar config's
+// declared type, falling back to the default. The flag is false when the default was used
+fn resolve_parameter(
+    mapping: &CommandMapping,
+    osc_state: &OscState,
+    normalize_names: bool,
+    default_type: &ParameterType,
+) -> (String, ParameterType, bool) {
+    let discovered = osc_state.find_parameter(&mapping.parameter_name, normalize_names);
+    let config_type = osc_state.config_parameter_type(&mapping.parameter_name);
+    match (discovered, config_type) {
+        (Some(param), _) => (param.name, param.parameter_type, true),
+        (None, Some(config_type)) => (mapping.parameter_name.clone(), config_type, true),
+        (None, None) => (mapping.parameter_name.clone(), default_type.clone(), false),
+    }
+}
+
+// Compile a "color {x}" template into a regex capturing the placeholder word, lowercasing the
+// template when matching against lowercased text
+fn placeholder_regex(template: &str, fold_case: bool) -> Result<Regex, String> {
+    let start = template.find('{').ok_or("Lookup command needs a {placeholder}")?;
+    let end = template[start..]
+        .find('}')
+        .map(|i| start + i)
+        .ok_or("Unclosed {placeholder} in lookup command")?;
+    let (before, after) = (&template[..start], &template[end + 1..]);
+    if before.contains('{') || after.contains('{') || after.contains('}') {
+        return Err("Lookup command must contain exactly one {placeholder}".to_string());
+    }
+    
+    let fold = |part: &str| if fold_case { part.to_lowercase() } else { part.to_string() };
+    let pattern = format!(r"\b{}(\w+){}", regex::escape(&fold(before)), regex::escape(&fold(after)));
+    Regex::new(&pattern).map_err(|e| format!("Invalid lookup command: {}", e))
+}
+
+// Compile the matcher a Regex or Lookup mapping runs against each utterance. Invalid patterns
+// leave it unset, so the mapping never fires
+fn compile_matcher(mapping: &mut CommandMapping, fold_case: bool) {
+    let compiled = match mapping.match_kind {
+        MatchKind::Regex => Regex::new(&mapping.command_text).map_err(|e| e.to_string()),
+        MatchKind::Lookup => placeholder_regex(&mapping.command_text, fold_case),
+        MatchKind::Phrase | MatchKind::AnyNumber => {
+            mapping.matcher = None;
+            return;
+        }
+    };
+    
+    mapping.matcher = compiled
+        .map_err(|e| log::warn!("Command {} will never match: {}", mapping.command_text, e))
+        .ok();
+}
+
+// Reject mappings that could never fire: bad regexes, and lookups without a usable table
+fn validate_mapping(mapping: &CommandMapping) -> Result<(), String> {
+    if mapping.match_kind == MatchKind::Regex {
+        Regex::new(&mapping.command_text).map_err(|e| format!("Invalid regex: {}", e))?;
+    }
+    if mapping.match_kind == MatchKind::Lookup {
+        placeholder_regex(&mapping.command_text, true)?;
+        match &mapping.lookup {
+            Some(table) if !table.is_empty() => {}
+            _ => return Err("Lookup commands need a non-empty lookup table".to_string()),
+        }
+    }
+    Ok(())
+}
+
+// Replace a mapping with the same command text and parameter, or append it. Returns whether an
+// existing mapping was replaced
+fn insert_mapping(language_commands: &mut Vec<CommandMapping>, mapping: CommandMapping) -> bool {
+    let existing_idx = language_commands.iter().position(|cmd| {
+        cmd.command_text == mapping.command_text && cmd.parameter_name == mapping.parameter_name
+    });
+    
+    match existing_idx {
+        Some(idx) => {
+            language_commands[idx] = mapping;
+            true
+        }
+        None => {
+            language_commands.push(mapping);
+            false
+        }
+    }
+}
+
+// Lookup table keys match case-insensitively when folding case (`word` is then lowercase)
+fn lookup_value(table: Option<&HashMap<String, f32>>, word: &str, fold_case: bool) -> Option<f32> {
+    table?
+        .iter()
+        .find(|(key, _)| if fold_case { key.to_lowercase() == word } else { key.as_str() == word })
+        .map(|(_, value)| *value)
+}
+
+// Check the fixed values a mapping can send against the target parameter's type. Captured
+// numbers (AnyNumber, Regex) are only known at speech time and aren't checked here
+fn validate_mapping_values(mapping: &CommandMapping, param_type: &ParameterType) -> Result<(), String> {
+    let scale = |value: f32| if mapping.as_percentage { percentage_to_unit(value) } else { value };
+    match mapping.match_kind {
+        MatchKind::Phrase => param_type.validate_value(scale(mapping.value)),
+        MatchKind::Lookup => mapping
+            .lookup
+            .iter()
+            .flatten()
+            .try_for_each(|(word, value)| {
+                param_type.validate_value(scale(*value)).map_err(|e| format!("{}: {}", word, e))
+            }),
+        MatchKind::AnyNumber | MatchKind::Regex => Ok(()),
+    }
+}
+
+fn percentage_to_unit(percent: f32) -> f32 {
+    (percent / 100.0).clamp(0.0, 1.0)
+}
+
+// Parse the first capture group of a regex match as a number
+fn captured_number(captures: &regex::Captures) -> Option<f32> {
+    captures.get(1).and_then(|m| m.as_str().trim().parse::<f32>().ok())
+}
+
+// Compile a pattern and run it against sample text without saving anything
+pub fn test_regex(pattern: &str, sample_text: &str) -> RegexTestResult {
+    match Regex::new(pattern) {
+        Ok(regex) => {
+            let captures = regex.captures(sample_text);
+            RegexTestResult {
+                valid: true,
+                error: None,
+                matched: captures.is_some(),
+                captured_value: captures.as_ref().and_then(captured_number),
+            }
+        }
+        Err(e) => RegexTestResult {
+            valid: false,
+            error: Some(e.to_string()),
+            matched: false,
+            captured_value: None,
+        },
+    }
+}
+
+// Find the first number in the text, as digits or (optionally) spelled-out English words. A
+// leading `-`, "minus" or "negative" makes it negative
+fn extract_number(text: &str, spoken_numbers: bool) -> Option<f32> {
+    let mut spoken_total: Option<f32> = None;
+    let mut negative = false;
+    let signed = |value: f32, negative: bool| if negative { -value } else { value };
+    
+    for word in text.split_whitespace() {
+        // A hyphen at the start of a word is a sign; inside one it joins spelled-out numbers
+        // such as "twenty-five"
+        let word = match word.strip_prefix('-') {
+            Some(rest) if spoken_total.is_none() => {
+                negative = true;
+                rest
+            }
+            _ => word,
+        };
+        
+        for token in word.split('-') {
+            let token = token.trim_matches(|c: char| !c.is_alphanumeric() && c != '.');
+            if token.is_empty() {
+                continue;
+            }
+            
+            // Checking the first char keeps words like "inf" and "nan" from parsing as floats
+            if token.starts_with(|c: char| c.is_ascii_digit()) {
+                if let Ok(number) = token.trim_end_matches('.').parse::<f32>() {
+                    return Some(signed(spoken_total.unwrap_or(number), negative));
+                }
+            }
+            
+            if spoken_total.is_none() && (token == "minus" || token == "negative") {
+                negative = true;
+                continue;
+            }
+            
+            let spoken = if spoken_numbers { spoken_number_value(token) } else { None };
+            match spoken {
+                Some(SpokenNumber::Hundred) => {
+                    if let Some(total) = spoken_total.as_mut() {
+                        *total *= 100.0;
+                    }
+                }
+                Some(SpokenNumber::Value(value)) => {
+                    spoken_total = Some(spoken_total.unwrap_or(0.0) + value);
+                }
+                None if token == "and" && spoken_total.is_some() => {}
+                None => {
+                    // The number ends at the first word that isn't part of it, and a sign only
+                    // applies to the word right after it
+                    if let Some(total) = spoken_total {
+                        return Some(signed(total, negative));
+                    }
+                    negative = false;
+                }
+            }
+        }
+    }
+    
+    spoken_total.map(|total| signed(total, negative))
+}
 
+enum SpokenNumber {
+    Value(f32),
+    Hundred,
+}
+
+fn spoken_number_value(word: &str) -> Option<SpokenNumber> {
+    const UNITS: [&str; 20] = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+        "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+    ];
+    const TENS: [&str; 8] = ["twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+    
+    if word == "hundred" {
+        return Some(SpokenNumber::Hundred);
+    }
+    if let Some(idx) = UNITS.iter().position(|w| *w == word) {
+        return Some(SpokenNumber::Value(idx as f32));
+    }
+    TENS.iter()
+        .position(|w| *w == word)
+        .map(|idx| SpokenNumber::Value((idx as f32 + 2.0) * 10.0))
+}
+
+// Tauri commands
+
+// Every mapping option is a separate optional argument so older callers keep working
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub fn add_command(
     language: &str,
     command_text: &str,
     parameter_name: &str,
     value: f32,
+    match_kind: Option<MatchKind>,
+    condition: Option<CommandCondition>,
+    as_percentage: Option<bool>,
+    target: Option<TargetKind>,
+    lookup: Option<HashMap<String, f32>>,
+    string_value: Option<String>,
     speech_state: State<SpeechState>,
+    osc_state: State<OscState>,
 ) -> Result<(), String> {
-    let mapping = CommandMapping {
+    let mut mapping = CommandMapping {
         command_text: command_text.to_string(),
         parameter_name: parameter_name.to_string(),
         value,
+        match_kind: match_kind.unwrap_or_default(),
+        condition,
+        as_percentage: as_percentage.unwrap_or(false),
+        target: target.unwrap_or_default(),
+        lookup,
+        string_value,
+        unverified: false,
+        matcher: None,
     };
     
+    speech_state.verify_mapping_values(&mut mapping, &osc_state)?;
     speech_state.add_command_mapping(language, mapping)
 }
 
+// Add or update many mappings at once, saving commands.json a single time
+#[tauri::command]
+pub fn add_commands(
+    language: &str,
+    mappings: Vec<CommandMapping>,
+    speech_state: State<SpeechState>,
+    osc_state: State<OscState>,
+) -> Result<Vec<AddCommandResult>, String> {
+    speech_state.add_command_mappings(language, mappings, &osc_state)
+}
+
 #[tauri::command]
 pub fn remove_command(
     language: &str,
@@ -203,17 +1311,543 @@ pub fn remove_command(
     speech_state.remove_command_mapping(language, command_text, parameter_name)
 }
 
+#[tauri::command]
+pub fn copy_command(
+    from_language: &str,
+    to_language: &str,
+    command_text: &str,
+    parameter_name: &str,
+    speech_state: State<SpeechState>,
+) -> Result<(), String> {
+    speech_state.copy_command_mapping(from_language, to_language, command_text, parameter_name)
+}
+
 #[tauri::command]
 pub fn get_command_mappings(language: &str, speech_state: State<SpeechState>) -> Vec<CommandMapping> {
     speech_state.get_commands(language)
 }
 
+#[tauri::command]
+pub fn set_default_parameter_type(
+    language: Option<&str>,
+    param_type_str: Option<&str>,
+    speech_state: State<SpeechState>,
+) -> Result<(), String> {
+    let param_type = param_type_str.map(parse_parameter_type).transpose()?;
+    speech_state.set_default_parameter_type(language, param_type)
+}
+
+#[tauri::command]
+pub fn validate_commands(
+    language: Option<&str>,
+    speech_state: State<SpeechState>,
+    osc_state: State<OscState>,
+) -> Vec<UnknownCommandParameter> {
+    speech_state.validate_commands(language, &osc_state)
+}
+
+#[tauri::command]
+pub fn set_case_insensitive_matching(
+    language: Option<&str>,
+    enabled: Option<bool>,
+    speech_state: State<SpeechState>,
+) -> Result<(), String> {
+    speech_state.set_case_insensitive_matching(language, enabled)
+}
+
+#[tauri::command]
+pub fn set_normalize_parameter_names(enabled: bool, speech_state: State<SpeechState>) -> Result<(), String> {
+    speech_state.set_normalize_parameter_names(enabled)
+}
+
+#[tauri::command]
+pub fn set_speech_enabled(enabled: bool, speech_state: State<SpeechState>) -> Result<(), String> {
+    speech_state.set_speech_enabled(enabled)
+}
+
+#[tauri::command]
+pub fn is_speech_enabled(speech_state: State<SpeechState>) -> bool {
+    speech_state.is_speech_enabled()
+}
+
+#[tauri::command]
+pub fn set_spoken_numbers(enabled: bool, speech_state: State<SpeechState>) -> Result<(), String> {
+    speech_state.set_spoken_numbers(enabled)
+}
+
+#[tauri::command]
+pub fn set_commands_watch(enabled: bool, speech_state: State<SpeechState>) -> Result<(), String> {
+    speech_state.set_commands_watch(enabled)
+}
+
+#[tauri::command]
+pub fn get_default_parameter_type(language: &str, speech_state: State<SpeechState>) -> ParameterType {
+    speech_state.get_default_parameter_type(language)
+}
+
+#[tauri::command]
+pub fn merge_languages(from: &str, to: &str, speech_state: State<SpeechState>) -> Result<MergeResult, String> {
+    speech_state.merge_languages(from, to)
+}
+
+#[tauri::command]
+pub fn get_all_command_mappings(speech_state: State<SpeechState>) -> HashMap<String, Vec<CommandMapping>> {
+    speech_state.get_all_commands()
+}
+
+#[tauri::command]
+pub fn count_commands(speech_state: State<SpeechState>) -> HashMap<String, usize> {
+    speech_state.count_commands()
+}
+
+#[tauri::command]
+pub fn test_speech_regex(pattern: &str, sample_text: &str) -> RegexTestResult {
+    test_regex(pattern, sample_text)
+}
+
 #[tauri::command]
 pub fn process_speech(
     text: &str,
     language: &str,
     speech_state: State<SpeechState>,
     osc_state: State<OscState>,
-) -> Result<Vec<String>, String> {
+) -> Result<SpeechResult, String> {
     speech_state.process_speech_input(text, language, &osc_state)
-} 
\ No newline at end of file
+}
+
+#[tauri::command]
+pub fn preview_speech_matches(
+    text: &str,
+    language: &str,
+    speech_state: State<SpeechState>,
+    osc_state: State<OscState>,
+) -> Vec<SpeechMatchPreview> {
+    speech_state.preview_matches(text, language, &osc_state)
+}
+
+#[tauri::command]
+pub fn get_speech_history(speech_state: State<SpeechState>) -> Vec<SpeechHistoryEntry> {
+    speech_state.get_history()
+}
+
+#[tauri::command]
+pub fn get_speech_stats(speech_state: State<SpeechState>) -> SpeechStats {
+    speech_state.get_stats()
+}
+
+#[tauri::command]
+pub fn clear_speech_history(speech_state: State<SpeechState>) {
+    speech_state.clear_history()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::osc::test_support::{self, capture_osc, recv_message};
+    use crate::osc::Parameter;
+    use rosc::OscType;
+    use std::net::UdpSocket;
+
+    // State whose commands and settings go to a fresh temporary directory
+    fn test_state() -> SpeechState {
+        let state = SpeechState::new();
+        *state.commands_dir.lock().unwrap() = Some(test_support::temp_dir("speech-test"));
+        state
+    }
+
+    fn mapping(command_text: &str, parameter_name: &str, value: f32) -> CommandMapping {
+        CommandMapping {
+            command_text: command_text.to_string(),
+            parameter_name: parameter_name.to_string(),
+            value,
+            match_kind: MatchKind::default(),
+            condition: None,
+            as_percentage: false,
+            target: TargetKind::default(),
+            lookup: None,
+            string_value: None,
+            unverified: false,
+            matcher: None,
+        }
+    }
+
+    #[test]
+    fn disabled_speech_is_distinct_from_no_match() {
+        let state = test_state();
+        let osc_state = OscState::new();
+        
+        let result = state.process_speech_input("hello", "en-US", &osc_state).unwrap();
+        assert!(!result.disabled);
+        assert!(result.matched.is_empty());
+        
+        state.settings.lock().unwrap().enabled = false;
+        let result = state.process_speech_input("hello", "en-US", &osc_state).unwrap();
+        assert!(result.disabled);
+        assert!(result.matched.is_empty());
+    }
+
+    #[test]
+    fn extracts_digits() {
+        assert_eq!(extract_number("set it to 5 please", false), Some(5.0));
+        assert_eq!(extract_number("volume 0.25.", false), Some(0.25));
+    }
+
+    #[test]
+    fn keeps_a_leading_minus() {
+        assert_eq!(extract_number("-0.5", false), Some(-0.5));
+        assert_eq!(extract_number("tilt -3 degrees", false), Some(-3.0));
+        assert_eq!(extract_number("minus 0.5", false), Some(-0.5));
+        assert_eq!(extract_number("negative 2", false), Some(-2.0));
+        assert_eq!(extract_number("minus twenty", true), Some(-20.0));
+    }
+
+    #[test]
+    fn minus_only_applies_to_the_next_word() {
+        assert_eq!(extract_number("minus the lights to 5", false), Some(5.0));
+    }
+
+    #[test]
+    fn extracts_spoken_numbers() {
+        assert_eq!(extract_number("twenty-five", true), Some(25.0));
+        assert_eq!(extract_number("one hundred and five", true), Some(105.0));
+        assert_eq!(extract_number("twenty-five", false), None);
+    }
+
+    #[test]
+    fn ignores_non_numbers() {
+        assert_eq!(extract_number("inf", true), None);
+        assert_eq!(extract_number("nan", false), None);
+        assert_eq!(extract_number("no numbers here", true), None);
+    }
+
+    #[test]
+    fn undiscovered_parameter_uses_language_default_type() {
+        let state = test_state();
+        let (osc_state, receiver) = capture_osc();
+        state.set_default_parameter_type(Some("en-US"), Some(ParameterType::Bool)).unwrap();
+        state.add_command_mapping("en-US", mapping("lights on", "Lights", 1.0)).unwrap();
+        
+        state.process_speech_input("lights on", "en-US", &osc_state).unwrap();
+        let msg = recv_message(&receiver);
+        assert_eq!(msg.addr, "/avatar/parameters/Lights");
+        assert_eq!(msg.args, vec![OscType::Bool(true)]);
+    }
+
+    #[test]
+    fn undiscovered_parameter_falls_back_to_float() {
+        let state = test_state();
+        let (osc_state, receiver) = capture_osc();
+        state.set_default_parameter_type(Some("de-DE"), Some(ParameterType::Bool)).unwrap();
+        state.add_command_mapping("en-US", mapping("lights on", "Lights", 1.0)).unwrap();
+        
+        state.process_speech_input("lights on", "en-US", &osc_state).unwrap();
+        assert_eq!(recv_message(&receiver).args, vec![OscType::Float(1.0)]);
+    }
+
+    fn assert_nothing_sent(receiver: &UdpSocket) {
+        receiver.set_read_timeout(Some(Duration::from_millis(100))).unwrap();
+        assert!(receiver.recv_from(&mut [0u8; 1024]).is_err());
+    }
+
+    fn guarded_turn_off() -> CommandMapping {
+        CommandMapping {
+            condition: Some(CommandCondition {
+                parameter_name: "Lights".to_string(),
+                condition: WatchCondition::GreaterThan { threshold: 0.5 },
+            }),
+            ..mapping("turn off", "Lights", 0.0)
+        }
+    }
+
+    #[test]
+    fn guarded_command_fires_when_condition_holds() {
+        let state = test_state();
+        let (osc_state, receiver) = capture_osc();
+        osc_state.store_parameter(Parameter::new("Lights", ParameterType::Bool, 1.0));
+        state.add_command_mapping("en-US", guarded_turn_off()).unwrap();
+        
+        let result = state.process_speech_input("turn off", "en-US", &osc_state).unwrap();
+        assert_eq!(result.matched, vec!["turn off -> Lights: 0"]);
+        assert_eq!(recv_message(&receiver).args, vec![OscType::Bool(false)]);
+    }
+
+    #[test]
+    fn guarded_command_is_skipped_when_condition_fails() {
+        let state = test_state();
+        let (osc_state, receiver) = capture_osc();
+        osc_state.store_parameter(Parameter::new("Lights", ParameterType::Bool, 0.0));
+        state.add_command_mapping("en-US", guarded_turn_off()).unwrap();
+        
+        let result = state.process_speech_input("turn off", "en-US", &osc_state).unwrap();
+        assert_eq!(result.matched, vec!["turn off -> Lights: skipped (condition)"]);
+        assert_nothing_sent(&receiver);
+    }
+
+    #[test]
+    fn guard_on_unknown_parameter_never_holds() {
+        let state = test_state();
+        let (osc_state, receiver) = capture_osc();
+        state.add_command_mapping("en-US", guarded_turn_off()).unwrap();
+        
+        let result = state.process_speech_input("turn off", "en-US", &osc_state).unwrap();
+        assert_eq!(result.matched, vec!["turn off -> Lights: skipped (condition)"]);
+        assert_nothing_sent(&receiver);
+    }
+
+    #[test]
+    fn percentages_scale_to_unit_range() {
+        assert_eq!(percentage_to_unit(0.0), 0.0);
+        assert_eq!(percentage_to_unit(50.0), 0.5);
+        assert_eq!(percentage_to_unit(100.0), 1.0);
+    }
+
+    #[test]
+    fn out_of_range_percentages_are_clamped() {
+        assert_eq!(percentage_to_unit(150.0), 1.0);
+        assert_eq!(percentage_to_unit(-20.0), 0.0);
+    }
+
+    #[test]
+    fn percentage_command_sends_and_reports_the_scaled_value() {
+        let state = test_state();
+        let (osc_state, receiver) = capture_osc();
+        let brightness = CommandMapping {
+            match_kind: MatchKind::Regex,
+            as_percentage: true,
+            ..mapping(r"brightness (\d+) percent", "Brightness", 0.0)
+        };
+        state.add_command_mapping("en-US", brightness).unwrap();
+        
+        let result = state.process_speech_input("set brightness 75 percent", "en-US", &osc_state).unwrap();
+        assert_eq!(result.matched, vec![r"brightness (\d+) percent -> Brightness: 0.75"]);
+        assert_eq!(recv_message(&receiver).args, vec![OscType::Float(0.75)]);
+        
+        state.process_speech_input("set brightness 250 percent", "en-US", &osc_state).unwrap();
+        assert_eq!(recv_message(&receiver).args, vec![OscType::Float(1.0)]);
+    }
+
+    #[test]
+    fn input_command_presses_and_releases_the_button() {
+        let state = test_state();
+        let (osc_state, receiver) = capture_osc();
+        let jump = CommandMapping {
+            target: TargetKind::Input,
+            ..mapping("jump", "Jump", 1.0)
+        };
+        state.add_command_mapping("en-US", jump).unwrap();
+        
+        let result = state.process_speech_input("jump", "en-US", &osc_state).unwrap();
+        assert_eq!(result.matched, vec!["jump -> input Jump: 1"]);
+        let press = recv_message(&receiver);
+        assert_eq!(press.addr, "/input/Jump");
+        assert_eq!(press.args, vec![OscType::Int(1)]);
+        let release = recv_message(&receiver);
+        assert_eq!(release.addr, "/input/Jump");
+        assert_eq!(release.args, vec![OscType::Int(0)]);
+    }
+
+    #[test]
+    fn input_command_sends_axis_values() {
+        let state = test_state();
+        let (osc_state, receiver) = capture_osc();
+        let forward = CommandMapping {
+            target: TargetKind::Input,
+            ..mapping("forward", "Vertical", 1.0)
+        };
+        state.add_command_mapping("en-US", forward).unwrap();
+        
+        state.process_speech_input("walk forward", "en-US", &osc_state).unwrap();
+        let msg = recv_message(&receiver);
+        assert_eq!(msg.addr, "/input/Vertical");
+        assert_eq!(msg.args, vec![OscType::Float(1.0)]);
+    }
+
+    fn color_lookup() -> CommandMapping {
+        let table = [("red", 0.1), ("Blue", 0.2)].into_iter().map(|(word, value)| (word.to_string(), value)).collect();
+        CommandMapping {
+            match_kind: MatchKind::Lookup,
+            lookup: Some(table),
+            ..mapping("color {x}", "Color", 0.0)
+        }
+    }
+
+    #[test]
+    fn lookup_sends_the_value_for_a_known_token() {
+        let state = test_state();
+        let (osc_state, receiver) = capture_osc();
+        state.add_command_mapping("en-US", color_lookup()).unwrap();
+        
+        let result = state.process_speech_input("set color blue please", "en-US", &osc_state).unwrap();
+        assert_eq!(result.matched, vec!["color {x} -> Color: 0.2"]);
+        assert_eq!(recv_message(&receiver).args, vec![OscType::Float(0.2)]);
+    }
+
+    #[test]
+    fn lookup_ignores_unknown_tokens() {
+        let state = test_state();
+        let osc_state = OscState::new();
+        state.add_command_mapping("en-US", color_lookup()).unwrap();
+        
+        let result = state.process_speech_input("color purple", "en-US", &osc_state).unwrap();
+        assert!(result.matched.is_empty());
+        let result = state.process_speech_input("colour red", "en-US", &osc_state).unwrap();
+        assert!(result.matched.is_empty());
+    }
+
+    #[test]
+    fn lookup_without_a_table_is_rejected() {
+        let state = test_state();
+        let mapping = CommandMapping {
+            lookup: None,
+            ..color_lookup()
+        };
+        assert!(state.add_command_mapping("en-US", mapping).is_err());
+    }
+
+    #[test]
+    fn matchers_are_compiled_when_mappings_are_set() {
+        let state = test_state();
+        state.add_command_mapping("en-US", color_lookup()).unwrap();
+        assert!(state.get_commands("en-US")[0].matcher.is_some());
+        
+        // Matchers aren't persisted, so loading has to rebuild them
+        let reloaded = SpeechState::new();
+        *reloaded.commands_dir.lock().unwrap() = state.commands_dir.lock().unwrap().clone();
+        reloaded.load_commands().unwrap();
+        assert!(reloaded.get_commands("en-US")[0].matcher.is_some());
+    }
+
+    #[test]
+    fn string_command_sends_an_osc_string() {
+        let state = test_state();
+        let (osc_state, receiver) = capture_osc();
+        let greeting = CommandMapping {
+            string_value: Some("hello there".to_string()),
+            ..mapping("say hi", "Greeting", 0.0)
+        };
+        state.add_command_mapping("en-US", greeting).unwrap();
+        
+        let result = state.process_speech_input("say hi", "en-US", &osc_state).unwrap();
+        assert_eq!(result.matched, vec!["say hi -> Greeting: \"hello there\""]);
+        let msg = recv_message(&receiver);
+        assert_eq!(msg.addr, "/avatar/parameters/Greeting");
+        assert_eq!(msg.args, vec![OscType::String("hello there".to_string())]);
+    }
+
+    // Whether a fixed-value mapping onto a known parameter of `param_type` passes verification
+    fn verifies(param_type: ParameterType, value: f32) -> bool {
+        let state = test_state();
+        let osc_state = OscState::new();
+        osc_state.store_parameter(Parameter::new("Target", param_type, 0.0));
+        state.verify_mapping_values(&mut mapping("go", "Target", value), &osc_state).is_ok()
+    }
+
+    #[test]
+    fn bool_values_must_be_zero_or_one() {
+        assert!(verifies(ParameterType::Bool, 0.0));
+        assert!(verifies(ParameterType::Bool, 1.0));
+        assert!(!verifies(ParameterType::Bool, 2.5));
+        assert!(!verifies(ParameterType::Bool, 0.5));
+    }
+
+    #[test]
+    fn int_values_must_be_whole_and_in_range() {
+        assert!(verifies(ParameterType::Int, 3.0));
+        assert!(!verifies(ParameterType::Int, 3.5));
+        assert!(!verifies(ParameterType::Int, 300.0));
+        assert!(!verifies(ParameterType::Int, -1.0));
+    }
+
+    #[test]
+    fn float_values_must_be_in_range() {
+        assert!(verifies(ParameterType::Float, 0.5));
+        assert!(verifies(ParameterType::Float, -1.0));
+        assert!(!verifies(ParameterType::Float, 2.0));
+    }
+
+    #[test]
+    fn lookup_values_are_checked_per_word() {
+        let state = test_state();
+        let osc_state = OscState::new();
+        osc_state.store_parameter(Parameter::new("Color", ParameterType::Bool, 0.0));
+        
+        let error = state.verify_mapping_values(&mut color_lookup(), &osc_state).unwrap_err();
+        assert!(error.contains("Bool value must be 0 or 1"), "{}", error);
+    }
+
+    #[test]
+    fn unknown_parameter_is_accepted_but_unverified() {
+        let state = test_state();
+        let osc_state = OscState::new();
+        let mut unknown = mapping("go", "Missing", 2.5);
+        
+        state.verify_mapping_values(&mut unknown, &osc_state).unwrap();
+        assert!(unknown.unverified);
+    }
+
+    // Commands file contents, if anything has been saved yet
+    fn saved_commands(state: &SpeechState) -> Option<String> {
+        fs::read_to_string(state.get_commands_path().unwrap()).ok()
+    }
+
+    #[test]
+    fn batch_add_saves_once() {
+        let state = test_state();
+        let osc_state = OscState::new();
+        let mut mappings: Vec<CommandMapping> =
+            (0..100).map(|i| mapping(&format!("command {}", i), "Target", 1.0)).collect();
+        mappings.push(CommandMapping {
+            match_kind: MatchKind::Regex,
+            ..mapping("(unclosed", "Target", 1.0)
+        });
+        
+        let results = state.add_command_mappings("en-US", mappings, &osc_state).unwrap();
+        // One save after the batch, holding every accepted mapping
+        let saved = saved_commands(&state).unwrap();
+        assert_eq!(state.last_written.lock().unwrap().as_deref(), Some(saved.as_str()));
+        assert_eq!(saved.matches("\"command_text\"").count(), 100);
+        assert_eq!(results.iter().filter(|r| r.outcome == AddCommandOutcome::Added).count(), 100);
+        assert_eq!(results[100].outcome, AddCommandOutcome::Rejected);
+        assert_eq!(state.get_commands("en-US").len(), 100);
+    }
+
+    #[test]
+    fn batch_add_with_nothing_valid_does_not_save() {
+        let state = test_state();
+        let osc_state = OscState::new();
+        let invalid = CommandMapping {
+            match_kind: MatchKind::Regex,
+            ..mapping("(unclosed", "Target", 1.0)
+        };
+        
+        state.add_command_mappings("en-US", vec![invalid], &osc_state).unwrap();
+        assert!(state.last_written.lock().unwrap().is_none());
+        assert_eq!(saved_commands(&state), None);
+    }
+
+    #[test]
+    fn case_significant_language_matches_phrases_exactly() {
+        let state = test_state();
+        let (osc_state, _receiver) = capture_osc();
+        state.set_case_insensitive_matching(Some("de-DE"), Some(false)).unwrap();
+        state.add_command_mapping("de-DE", mapping("Licht an", "Lights", 1.0)).unwrap();
+        state.add_command_mapping("en-US", mapping("Lights on", "Lights", 1.0)).unwrap();
+        
+        assert!(state.process_speech_input("licht an", "de-DE", &osc_state).unwrap().matched.is_empty());
+        assert_eq!(state.process_speech_input("Licht an", "de-DE", &osc_state).unwrap().matched.len(), 1);
+        // Other languages keep following the global setting
+        assert_eq!(state.process_speech_input("lights on", "en-US", &osc_state).unwrap().matched.len(), 1);
+    }
+
+    #[test]
+    fn case_significant_language_matches_lookups_exactly() {
+        let state = test_state();
+        let (osc_state, _receiver) = capture_osc();
+        state.add_command_mapping("en-US", color_lookup()).unwrap();
+        state.set_case_insensitive_matching(Some("en-US"), Some(false)).unwrap();
+        
+        assert!(state.process_speech_input("Color red", "en-US", &osc_state).unwrap().matched.is_empty());
+        assert!(state.process_speech_input("color blue", "en-US", &osc_state).unwrap().matched.is_empty());
+        assert_eq!(state.process_speech_input("color Blue", "en-US", &osc_state).unwrap().matched.len(), 1);
+    }
+}
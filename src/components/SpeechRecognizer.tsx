@@ -16,8 +16,12 @@ export function SpeechRecognizer({ language }: SpeechRecognizerProps) {
         setRecognizedText(text);
         
         if (text.trim().length > 0) {
-          const processedCommands = await processSpeech(text, language);
-          console.log('Processed commands:', processedCommands);
+          const result = await processSpeech(text, language);
+          if (result.disabled) {
+            console.log('Speech commands are disabled');
+          } else {
+            console.log('Processed commands:', result.matched);
+          }
         }
       },
     },
@@ -1,7 +1,7 @@
 import { create } from 'zustand';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
-import { AppConfig, CommandMapping, OscConfig, Parameter } from '../types';
+import { AppConfig, CommandMapping, OscConfig, Parameter, SpeechResult } from '../types';
 import { persist } from 'zustand/middleware';
 
 export interface AppState {
@@ -19,7 +19,7 @@ export interface AppState {
   fetchCommands: (language: string) => Promise<void>;
   addCommand: (language: string, commandText: string, parameterName: string, value: number) => Promise<void>;
   removeCommand: (language: string, commandText: string, parameterName: string) => Promise<boolean>;
-  processSpeech: (text: string, language: string) => Promise<string[]>;
+  processSpeech: (text: string, language: string) => Promise<SpeechResult>;
   
   // Speech Recognition
   isListening: boolean;
@@ -109,7 +109,7 @@ export const useAppStore = create<AppState>()(
       },
       processSpeech: async (text, language) => {
         try {
-          return await invoke<string[]>('process_speech', { text, language });
+          return await invoke<SpeechResult>('process_speech', { text, language });
         } catch (error) {
           console.error('Failed to process speech:', error);
           throw error;
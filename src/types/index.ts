@@ -16,6 +16,12 @@ export interface CommandMapping {
   value: number;
 }
 
+// `disabled` is set when speech commands are turned off, as opposed to nothing matching
+export interface SpeechResult {
+  matched: string[];
+  disabled: boolean;
+}
+
 export interface OscConfig {
   targetAddress: string;
   targetPort: number;